@@ -245,6 +245,7 @@ fn mainnet_genesis(
 		pallet_elections_phragmen: Some(Default::default()),
 		pallet_treasury: Some(Default::default()),
 		pallet_indices: Some(IndicesConfig { indices: vec![] }),
+		pallet_grandao: Some(Default::default()),
 
 
 		pallet_session: Some(SessionConfig {
@@ -313,6 +314,7 @@ fn testnet_genesis(
 		pallet_elections_phragmen: Some(Default::default()),
 		pallet_treasury: Some(Default::default()),
 		pallet_indices: Some(IndicesConfig { indices: vec![] }),
+		pallet_grandao: Some(Default::default()),
 
 
 		pallet_session: Some(SessionConfig {
@@ -52,6 +52,8 @@ pub enum ClassType {
 	Transferable,
 	/// Token can be burned
 	Burnable,
+	/// Token can neither be transferred nor burned, minted by other pallets as credentials
+	Soulbound,
 }
 
 impl ClassType {
@@ -124,12 +126,21 @@ pub mod module {
 		type WeightInfo: WeightInfo;
 	}
 
-	/// 查询在一个class下的账户有多少nft
+	/// 查询在一个class下的账户有多少nft 铸造、转让与销毁时更新
 	#[pallet::storage]
 	#[pallet::getter(fn owned_tokens_count)]
 	pub type OwnedTokensCount<T: Config> = 
 		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::ClassId, u32, ValueQuery>;
 
+	/// OwnedTokensCount是否已按现有nft补全 升级时补全一次
+	#[pallet::storage]
+	pub(super) type OwnedTokensCountBuilt<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+
+	/// 凭证分组（如任务分类）与凭证class的关系 未分组的凭证使用None
+	#[pallet::storage]
+	#[pallet::getter(fn credential_class)]
+	pub type CredentialClasses<T: Config> = StorageMap<_, Twox64Concat, Option<u32>, ClassIdOf<T>, OptionQuery>;
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -175,7 +186,23 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// 旧版本未维护OwnedTokensCount，按现有nft补全
+		fn on_runtime_upgrade() -> Weight {
+			if OwnedTokensCountBuilt::<T>::get() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut count: Weight = 0;
+			for (owner, (class_id, _), _) in orml_nft::TokensByOwner::<T>::iter() {
+				OwnedTokensCount::<T>::mutate(&owner, class_id, |n| *n = n.saturating_add(1));
+				count += 1;
+			}
+			OwnedTokensCountBuilt::<T>::put(true);
+
+			T::DbWeight::get().reads_writes(count.saturating_mul(2).saturating_add(1), count.saturating_add(1))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -239,6 +266,7 @@ pub mod module {
 			let data = TokenData { deposit:total_deposit, };
 			
 			let token_id = orml_nft::Pallet::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+			OwnedTokensCount::<T>::mutate(&to, class_id, |n| *n = n.saturating_add(1));
 		
 			Self::deposit_event(Event::MintedToken(who, to, class_id, token_id));
 			Ok(().into())
@@ -289,6 +317,7 @@ pub mod module {
 			ensure!(who == token_info.owner, Error::<T>::NoPermission);
 
 			orml_nft::Pallet::<T>::burn(&who, token)?;
+			OwnedTokensCount::<T>::mutate(&who, token.0, |n| *n = n.saturating_sub(1));
 			let owner: T::AccountId = T::ModuleId::get().into_sub_account(token.0);
 			let data = token_info.data;
 			// `repatriate_reserved` will check `to` account exist and return `DeadAccount`.
@@ -339,6 +368,41 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// 铸造不可转让的凭证NFT，供其他模块（如grandao任务完成凭证）调用
+	/// 每个分组首次铸造时自动创建一个Soulbound class，class与token均由模块账户持有押金为0，
+	/// 不需要用户签名，也不向接收者收取押金
+	#[transactional]
+	pub fn mint_soulbound(group: Option<u32>, to: &T::AccountId, metadata: Metadata) -> Result<(ClassIdOf<T>, TokenIdOf<T>), DispatchError> {
+		let owner: T::AccountId = T::ModuleId::get().into_account();
+
+		let class_id = match CredentialClasses::<T>::get(group) {
+			Some(class_id) => class_id,
+			None => {
+				let data = ClassData {
+					deposit: Zero::zero(),
+					classtype: ClassType::Soulbound,
+					name: b"credential".to_vec(),
+					description: Vec::new(),
+				};
+				let class_id = orml_nft::Pallet::<T>::create_class(&owner, group.encode(), data)?;
+				CredentialClasses::<T>::insert(group, class_id);
+				Self::deposit_event(Event::CreatedClass(owner.clone(), class_id));
+				class_id
+			}
+		};
+
+		let token_id = orml_nft::Pallet::<T>::mint(to, class_id, metadata, TokenData { deposit: Zero::zero() })?;
+		OwnedTokensCount::<T>::mutate(to, class_id, |n| *n = n.saturating_add(1));
+
+		Self::deposit_event(Event::MintedToken(owner, to.clone(), class_id, token_id));
+		Ok((class_id, token_id))
+	}
+
+	/// 账户是否持有某class的任意一个token，供其他模块（如grandao按NFT限制投标）调用
+	pub fn holds_class(who: &T::AccountId, class_id: ClassIdOf<T>) -> bool {
+		OwnedTokensCount::<T>::get(who, class_id) > 0
+	}
+
 	/// Ensured atomic.
 	#[transactional]
 	fn do_transfer(from: &T::AccountId, to: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
@@ -353,6 +417,10 @@ impl<T: Config> Pallet<T> {
 		ensure!(*from == token_info.owner, Error::<T>::NoPermission);
 
 		orml_nft::Pallet::<T>::transfer(from, to, token)?;
+		if from != to {
+			OwnedTokensCount::<T>::mutate(from, token.0, |n| *n = n.saturating_sub(1));
+			OwnedTokensCount::<T>::mutate(to, token.0, |n| *n = n.saturating_add(1));
+		}
 
 		Self::deposit_event(Event::TransferredToken(from.clone(), to.clone(), token.0, token.1));
 		Ok(())
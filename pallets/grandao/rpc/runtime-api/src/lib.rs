@@ -0,0 +1,41 @@
+//! Runtime API definition for the grandao pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_grandao::{Reputation, TaskStatus, TaskView, Milestone, MilestoneStatus};
+
+sp_api::decl_runtime_apis! {
+	/// 任务查询API
+	pub trait GrandaoApi<AccountId, Hash, BlockNumber, CurrencyId> where
+		AccountId: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+		CurrencyId: Codec,
+	{
+		/// 查询任务的详情、状态、质押、投标人与中标人
+		fn task(task_id: u128) -> Option<TaskView<AccountId, Hash, BlockNumber, CurrencyId>>;
+		/// 按顺序查询任务的里程碑，包括每个里程碑的比例、金额与状态
+		fn milestones(task_id: u128) -> Vec<Milestone>;
+		/// 分页查询某账户创建的任务
+		fn tasks_by_owner(owner: AccountId, page: u32, page_size: u32) -> Vec<u128>;
+		/// 分页查询某账户投标的任务
+		fn tasks_by_bidder(bidder: AccountId, page: u32, page_size: u32) -> Vec<u128>;
+		/// 分页查询某账户中标的任务
+		fn tasks_by_receiver(receiver: AccountId, page: u32, page_size: u32) -> Vec<u128>;
+		/// 分页查询某状态的任务
+		fn tasks_by_status(status: TaskStatus, page: u32, page_size: u32) -> Vec<u128>;
+		/// 分页查询某分类的任务
+		fn tasks_by_category(category: u32, page: u32, page_size: u32) -> Vec<u128>;
+		/// 分页查询带有某标签的任务
+		fn tasks_by_tag(tag: Vec<u8>, page: u32, page_size: u32) -> Vec<u128>;
+		/// 分页查询某任务的子任务
+		fn subtasks(parent_id: u128, page: u32, page_size: u32) -> Vec<u128>;
+		/// 查询所有任务分类 (CategoryId, Name)
+		fn categories() -> Vec<(u32, Vec<u8>)>;
+		/// 查询账户的信誉记录
+		fn reputation(who: AccountId) -> Reputation;
+	}
+}
@@ -0,0 +1,145 @@
+//! RPC interface for the grandao pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_grandao_rpc_runtime_api::GrandaoApi as GrandaoRuntimeApi;
+use pallet_grandao_rpc_runtime_api::{Reputation, TaskStatus, TaskView};
+
+/// 任务查询RPC
+#[rpc]
+pub trait GrandaoApi<BlockHash, AccountId, Hash, BlockNumber, CurrencyId> {
+	/// 查询任务的详情、状态、质押、投标人与中标人
+	#[rpc(name = "grandao_task")]
+	fn task(&self, task_id: u128, at: Option<BlockHash>) -> Result<Option<TaskView<AccountId, Hash, BlockNumber, CurrencyId>>>;
+
+	/// 分页查询某账户创建的任务
+	#[rpc(name = "grandao_tasksByOwner")]
+	fn tasks_by_owner(&self, owner: AccountId, page: u32, page_size: u32, at: Option<BlockHash>) -> Result<Vec<u128>>;
+
+	/// 分页查询某账户投标的任务
+	#[rpc(name = "grandao_tasksByBidder")]
+	fn tasks_by_bidder(&self, bidder: AccountId, page: u32, page_size: u32, at: Option<BlockHash>) -> Result<Vec<u128>>;
+
+	/// 分页查询某账户中标的任务
+	#[rpc(name = "grandao_tasksByReceiver")]
+	fn tasks_by_receiver(&self, receiver: AccountId, page: u32, page_size: u32, at: Option<BlockHash>) -> Result<Vec<u128>>;
+
+	/// 分页查询某状态的任务
+	#[rpc(name = "grandao_tasksByStatus")]
+	fn tasks_by_status(&self, status: TaskStatus, page: u32, page_size: u32, at: Option<BlockHash>) -> Result<Vec<u128>>;
+
+	/// 分页查询某分类的任务
+	#[rpc(name = "grandao_tasksByCategory")]
+	fn tasks_by_category(&self, category: u32, page: u32, page_size: u32, at: Option<BlockHash>) -> Result<Vec<u128>>;
+
+	/// 分页查询带有某标签的任务
+	#[rpc(name = "grandao_tasksByTag")]
+	fn tasks_by_tag(&self, tag: Bytes, page: u32, page_size: u32, at: Option<BlockHash>) -> Result<Vec<u128>>;
+
+	/// 分页查询某任务的子任务
+	#[rpc(name = "grandao_subTasks")]
+	fn subtasks(&self, parent_id: u128, page: u32, page_size: u32, at: Option<BlockHash>) -> Result<Vec<u128>>;
+
+	/// 查询所有任务分类
+	#[rpc(name = "grandao_categories")]
+	fn categories(&self, at: Option<BlockHash>) -> Result<Vec<(u32, Bytes)>>;
+
+	/// 查询账户的信誉记录
+	#[rpc(name = "grandao_reputation")]
+	fn reputation(&self, who: AccountId, at: Option<BlockHash>) -> Result<Reputation>;
+}
+
+/// 任务查询RPC的实现
+pub struct Grandao<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Grandao<C, B> {
+	/// 创建任务查询RPC
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// 运行时调用出错
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query grandao tasks.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, Hash, BlockNumber, CurrencyId> GrandaoApi<<Block as BlockT>::Hash, AccountId, Hash, BlockNumber, CurrencyId>
+	for Grandao<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: GrandaoRuntimeApi<Block, AccountId, Hash, BlockNumber, CurrencyId>,
+	AccountId: Codec,
+	Hash: Codec,
+	BlockNumber: Codec,
+	CurrencyId: Codec,
+{
+	fn task(&self, task_id: u128, at: Option<<Block as BlockT>::Hash>) -> Result<Option<TaskView<AccountId, Hash, BlockNumber, CurrencyId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().task(&at, task_id).map_err(runtime_error)
+	}
+
+	fn tasks_by_owner(&self, owner: AccountId, page: u32, page_size: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u128>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().tasks_by_owner(&at, owner, page, page_size).map_err(runtime_error)
+	}
+
+	fn tasks_by_bidder(&self, bidder: AccountId, page: u32, page_size: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u128>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().tasks_by_bidder(&at, bidder, page, page_size).map_err(runtime_error)
+	}
+
+	fn tasks_by_receiver(&self, receiver: AccountId, page: u32, page_size: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u128>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().tasks_by_receiver(&at, receiver, page, page_size).map_err(runtime_error)
+	}
+
+	fn tasks_by_status(&self, status: TaskStatus, page: u32, page_size: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u128>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().tasks_by_status(&at, status, page, page_size).map_err(runtime_error)
+	}
+
+	fn tasks_by_category(&self, category: u32, page: u32, page_size: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u128>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().tasks_by_category(&at, category, page, page_size).map_err(runtime_error)
+	}
+
+	fn tasks_by_tag(&self, tag: Bytes, page: u32, page_size: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u128>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().tasks_by_tag(&at, tag.to_vec(), page, page_size).map_err(runtime_error)
+	}
+
+	fn subtasks(&self, parent_id: u128, page: u32, page_size: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<u128>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().subtasks(&at, parent_id, page, page_size).map_err(runtime_error)
+	}
+
+	fn categories(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(u32, Bytes)>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let categories = self.client.runtime_api().categories(&at).map_err(runtime_error)?;
+		Ok(categories.into_iter().map(|(id, name)| (id, name.into())).collect())
+	}
+
+	fn reputation(&self, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Reputation> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().reputation(&at, who).map_err(runtime_error)
+	}
+}
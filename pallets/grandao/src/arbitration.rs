@@ -0,0 +1,277 @@
+// 任务争议仲裁
+// 任务双方任意一方缴纳保证金发起仲裁，同时锁定另一方（被诉方）同等数额的保证金，从已注册并质押的仲裁员池中随机抽取仲裁小组，
+// 小组成员投票给出中标者应得的任务奖励比例，取投票中位数作为裁决。
+// 投票期内未全部投票时，未投票的仲裁员移出仲裁小组，以已投票的中位数裁决，无人投票则直接提交理事会判决；
+// 仲裁员在投票前可以回避，由新抽取的仲裁员替换。
+// 裁决后进入上诉期，任意一方可缴纳上诉费向理事会上诉（Judging），由理事会给出最终判决；
+// 上诉期内无人上诉则按仲裁裁决执行，并罚没败诉方的质押与保证金。
+// 针对里程碑的仲裁只拆分该里程碑的奖励，不罚没中标者的投标质押（最后一个里程碑除外），
+// 裁决执行后任务回到进行中，继续交付后续里程碑
+
+use super::*;
+use frame_support::traits::Randomness;
+use sp_runtime::traits::Hash;
+
+/// 争议案件
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Dispute<AccountId, Hash, BlockNumber> {
+    /// 仲裁发起人
+    pub plaintiff: AccountId,
+    /// 发起人缴纳的保证金
+    pub bond: Balance,
+    /// 被诉方 发起人为中标团队成员时为任务创建者，否则为中标者
+    pub defendant: AccountId,
+    /// 被诉方锁定的保证金 可用余额不足时锁定全部可用余额，余额被冻结而无法锁定时为0
+    pub defendant_bond: Balance,
+    /// 仲裁小组成员
+    pub panel: Vec<AccountId>,
+    /// 双方提交的证据哈希 (提交人, 证据哈希)
+    pub evidence: Vec<(AccountId, Hash)>,
+    /// 发起仲裁的区块高度
+    pub opened_at: BlockNumber,
+    /// 仲裁小组的投票截止区块
+    pub ruling_deadline: BlockNumber,
+}
+
+pub type DisputeOf<T> = Dispute<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::Hash, <T as frame_system::Config>::BlockNumber>;
+
+impl<T: Config> Module<T> {
+    /// 发起人缴纳保证金并锁定被诉方的保证金，抽取仲裁小组并将任务变更为仲裁中
+    pub(crate) fn start_dispute(plaintiff: T::AccountId, task_id: u128, owner: T::AccountId, evidence: T::Hash) -> dispatch::DispatchResult {
+        // 缴纳仲裁保证金
+        let bond = T::DisputeBond::get();
+        ensure!(T::Currency::can_reserve(&plaintiff, bond), Error::<T>::InsufficientBalance);
+        T::Currency::reserve(&plaintiff, bond)?;
+
+        // 锁定被诉方的保证金 被诉方的保证金不足不影响发起仲裁
+        let defendant = if plaintiff == owner { RelTaskReceiver::<T>::get(&task_id) } else { owner.clone() };
+        let defendant_bond = Self::reserve_defendant_bond(&defendant, bond);
+
+        // 抽取仲裁小组 排除任务创建者与中标团队的所有成员
+        let mut parties: Vec<T::AccountId> = Self::task_team(task_id).into_iter().map(|(member, _)| member).collect();
+        parties.push(owner);
+        let panel = Self::draw_panel(task_id, &parties, T::ArbitratorPanelSize::get() as usize)?;
+        for arbitrator in panel.iter() {
+            ArbitratorCaseCount::<T>::mutate(arbitrator, |count| *count += 1);
+        }
+
+        let now = <frame_system::Module<T>>::block_number();
+        let dispute = Dispute {
+            plaintiff: plaintiff.clone(),
+            bond,
+            defendant,
+            defendant_bond,
+            panel: panel.clone(),
+            evidence: sp_std::vec![(plaintiff.clone(), evidence)],
+            opened_at: now,
+            ruling_deadline: now + T::RulingPeriod::get(),
+        };
+        Disputes::<T>::insert(&task_id, dispute);
+        Self::set_task_status(task_id, TaskStatus::Arbitrating);
+        Self::cancel_review(task_id);
+
+        Self::deposit_event(RawEvent::DisputeOpened(plaintiff.clone(), task_id, panel));
+        Self::deposit_event(RawEvent::TaskStatusUpdated(plaintiff, task_id, TaskStatus::Arbitrating));
+
+        Ok(())
+    }
+
+    /// 锁定被诉方的保证金，返回实际锁定的数量
+    /// 依次尝试锁定全额保证金与全部可用余额；可用余额被冻结（如锁仓）而无法锁定时不锁定
+    fn reserve_defendant_bond(defendant: &T::AccountId, bond: Balance) -> Balance {
+        let free = T::Currency::free_balance(defendant).min(bond);
+        for amount in [bond, free].iter() {
+            if T::Currency::can_reserve(defendant, *amount) && T::Currency::reserve(defendant, *amount).is_ok() {
+                return *amount;
+            }
+        }
+        0
+    }
+
+    /// 从仲裁员池中随机抽取`size`名仲裁员，排除任务双方
+    pub(crate) fn draw_panel(task_id: u128, exclude: &[T::AccountId], size: usize) -> Result<Vec<T::AccountId>, DispatchError> {
+        let mut candidates: Vec<T::AccountId> = Arbitrators::<T>::get()
+            .into_iter()
+            .filter(|a| !exclude.contains(a))
+            .collect();
+
+        ensure!(size > 0 && candidates.len() >= size, Error::<T>::NotEnoughArbitrators);
+
+        let seed = T::Randomness::random(&(b"grandao/panel", task_id, exclude.len() as u32).encode());
+        let mut panel = Vec::with_capacity(size);
+        let mut nonce: u32 = 0;
+        while panel.len() < size {
+            let random = T::Hashing::hash_of(&(seed, nonce));
+            let random_number = u32::decode(&mut random.as_ref()).unwrap_or_default();
+            let index = random_number as usize % candidates.len();
+            panel.push(candidates.swap_remove(index));
+            nonce += 1;
+        }
+
+        Ok(panel)
+    }
+
+    /// 从仲裁员池中抽取一名仲裁员替换回避的仲裁员，排除任务双方与仲裁小组现有成员
+    pub(crate) fn replace_arbitrator(task_id: u128, dispute: &mut DisputeOf<T>, arbitrator: &T::AccountId) -> Result<T::AccountId, DispatchError> {
+        let owner = TaskDetail::<T>::get(&task_id).owner;
+        let mut exclude: Vec<T::AccountId> = Self::task_team(task_id).into_iter().map(|(member, _)| member).collect();
+        exclude.push(owner);
+        exclude.extend(dispute.panel.iter().cloned());
+
+        let replacement = Self::draw_panel(task_id, &exclude, 1)?.remove(0);
+        ArbitratorCaseCount::<T>::mutate(arbitrator, |count| *count = count.saturating_sub(1));
+        ArbitratorCaseCount::<T>::mutate(&replacement, |count| *count += 1);
+        dispute.panel.retain(|a| a != arbitrator);
+        dispute.panel.push(replacement.clone());
+
+        Ok(replacement)
+    }
+
+    /// 投票期结束后将未投票的仲裁员移出仲裁小组，不再分得败诉方的保证金
+    pub(crate) fn remove_absent_arbitrators(task_id: u128, dispute: &mut DisputeOf<T>) {
+        let (present, absent): (Vec<T::AccountId>, Vec<T::AccountId>) = dispute.panel.drain(..)
+            .partition(|arbitrator| DisputeVotes::<T>::contains_key(&task_id, arbitrator));
+        dispute.panel = present;
+
+        if absent.is_empty() {
+            return;
+        }
+        for arbitrator in absent.iter() {
+            ArbitratorCaseCount::<T>::mutate(arbitrator, |count| *count = count.saturating_sub(1));
+        }
+        Self::deposit_event(RawEvent::AbsentArbitratorsRemoved(task_id, absent));
+    }
+
+    /// 仲裁小组全部投票后，以投票的中位数作为裁决，并进入上诉期
+    pub(crate) fn record_ruling(task_id: u128, dispute: &DisputeOf<T>) -> dispatch::DispatchResult {
+        let mut votes: Vec<Permill> = dispute.panel.iter()
+            .filter_map(|arbitrator| DisputeVotes::<T>::get(&task_id, arbitrator))
+            .collect();
+        ensure!(!votes.is_empty(), Error::<T>::NoSuchDispute);
+        votes.sort();
+        let receiver_share = votes[(votes.len() - 1) / 2];
+
+        let appeal_deadline = <frame_system::Module<T>>::block_number() + T::AppealPeriod::get();
+        DisputeRulings::<T>::insert(&task_id, (receiver_share, appeal_deadline));
+
+        Self::deposit_event(RawEvent::DisputeRuled(task_id, receiver_share, appeal_deadline));
+
+        Ok(())
+    }
+
+    /// 执行裁决
+    /// - 任务奖励按`receiver_share`比例支付给中标者（团队任务再按成员比例拆分），其余退还任务创建者
+    /// - 中标者败诉时所有中标团队成员的投标质押罚没给任务创建者，否则退还
+    /// - 败诉方（仲裁发起人或被诉方）的保证金罚没并平分给仲裁小组，胜诉方的保证金退还
+    /// - 双方各占一半视为平局，不罚没任何质押
+    pub(crate) fn execute_ruling(task_id: u128, dispute: DisputeOf<T>, receiver_share: Permill) -> dispatch::DispatchResult {
+        let owner = TaskDetail::<T>::get(&task_id).owner;
+
+        let half = Permill::from_percent(50);
+        let receiver_lost = receiver_share < half;
+        let owner_lost = receiver_share > half;
+
+        // 针对里程碑的仲裁只涉及该里程碑的奖励，最后一个里程碑按整个任务结算
+        let milestone = DisputedMilestone::take(&task_id);
+        let final_settlement = milestone.map_or(true, |index| Self::is_last_milestone(task_id, index));
+        let reward = match milestone {
+            Some(index) if !final_settlement => {
+                let amount = TaskMilestones::get(&task_id).get(index as usize)
+                    .map(|m| m.amount)
+                    .ok_or(Error::<T>::NoSuchMilestone)?;
+                Self::take_tranche(task_id, amount)?;
+                amount
+            },
+            _ => RelCreateTaskStake::take(&task_id),
+        };
+        if let Some(index) = milestone {
+            Self::set_milestone_status(task_id, index, MilestoneStatus::Paid);
+        }
+
+        // 按比例拆分任务奖励
+        let to_receiver = receiver_share * reward;
+        let to_owner = reward.saturating_sub(to_receiver);
+        Self::pay_team(task_id, &owner, to_receiver)?;
+        Self::refund_escrow(task_id, &owner, to_owner)?;
+
+        // 中标者的投标质押 任务结束时才处理
+        if final_settlement {
+            if receiver_lost {
+                Self::slash_team_stakes(task_id, &owner)?;
+            } else {
+                Self::release_team_stakes(task_id);
+            }
+        }
+
+        // 双方的保证金 平局时均退还
+        let plaintiff_is_owner = dispute.plaintiff == owner;
+        let plaintiff_lost = (plaintiff_is_owner && owner_lost) || (!plaintiff_is_owner && receiver_lost);
+        let defendant_lost = (plaintiff_is_owner && receiver_lost) || (!plaintiff_is_owner && owner_lost);
+        if plaintiff_lost {
+            Self::slash_bond_to_panel(&dispute, &dispute.plaintiff, dispute.bond)?;
+        } else {
+            T::Currency::unreserve(&dispute.plaintiff, dispute.bond);
+        }
+        if defendant_lost {
+            Self::slash_bond_to_panel(&dispute, &dispute.defendant, dispute.defendant_bond)?;
+        } else {
+            T::Currency::unreserve(&dispute.defendant, dispute.defendant_bond);
+        }
+
+        // 更新双方的信誉记录 任务结束且中标者未败诉时记为完成，并铸造任务完成凭证
+        let team: Vec<T::AccountId> = Self::task_team(task_id).into_iter().map(|(member, _)| member).collect();
+        Self::record_dispute_outcome(&owner, &team, receiver_share);
+        if final_settlement && !receiver_lost {
+            for member in team.iter() {
+                Self::update_reputation(member, |r| r.completed = r.completed.saturating_add(1));
+                Self::issue_credential(task_id, &owner, member);
+            }
+        }
+
+        Self::close_dispute(task_id, &dispute);
+        let task_status = if final_settlement { TaskStatus::Finished } else { TaskStatus::Doing };
+        Self::set_task_status(task_id, task_status);
+
+        Self::deposit_event(RawEvent::DisputeResolved(task_id, receiver_share, to_receiver, to_owner));
+
+        Ok(())
+    }
+
+    /// 撤回争议：退还双方的保证金并解散仲裁小组
+    pub(crate) fn dismiss_dispute(task_id: u128) {
+        if let Some(dispute) = Disputes::<T>::get(&task_id) {
+            T::Currency::unreserve(&dispute.plaintiff, dispute.bond);
+            T::Currency::unreserve(&dispute.defendant, dispute.defendant_bond);
+            Self::close_dispute(task_id, &dispute);
+            Self::deposit_event(RawEvent::DisputeDismissed(task_id));
+        }
+    }
+
+    /// 将败诉方的保证金平分给仲裁小组，不能整除的余数归第一位仲裁员
+    fn slash_bond_to_panel(dispute: &DisputeOf<T>, loser: &T::AccountId, bond: Balance) -> dispatch::DispatchResult {
+        let members = dispute.panel.len() as Balance;
+        if members == 0 {
+            T::Currency::unreserve(loser, bond);
+            return Ok(());
+        }
+
+        let share = bond / members;
+        let remainder = bond % members;
+        for (i, arbitrator) in dispute.panel.iter().enumerate() {
+            let amount = if i == 0 { share + remainder } else { share };
+            T::Currency::repatriate_reserved(loser, arbitrator, amount, BalanceStatus::Free)?;
+        }
+
+        Ok(())
+    }
+
+    /// 清除争议案件的存储，并释放仲裁小组成员
+    fn close_dispute(task_id: u128, dispute: &DisputeOf<T>) {
+        for arbitrator in dispute.panel.iter() {
+            ArbitratorCaseCount::<T>::mutate(arbitrator, |count| *count = count.saturating_sub(1));
+        }
+        DisputeVotes::<T>::remove_prefix(&task_id);
+        DisputeRulings::<T>::remove(&task_id);
+        Disputes::<T>::remove(&task_id);
+    }
+}
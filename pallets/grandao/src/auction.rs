@@ -0,0 +1,54 @@
+// 自动选标（反向竞价）
+// 任务创建者可以在收到投标前将任务设置为自动选标，并指定中标所需的信誉要求，任务必须设置投标截止区块。
+// 投标截止时（密封投标的任务为揭示截止时）由on_initialize自动选标：
+// 在投标方案的预计交付区块尚未到达、且信誉记录满足要求的投标人中，报价最低者中标，
+// 报价相同时先投标者中标（修改投标视为重新投标）；其余投标人的质押全部退还。没有满足要求的投标时任务自动撤销。
+// 自动选标的任务不能手动选标
+
+use super::*;
+
+impl<T: Config> Module<T> {
+    /// 检查自动选标的设置：必须设置投标截止区块
+    pub(crate) fn ensure_valid_auto_award(task_id: u128) -> dispatch::DispatchResult {
+        ensure!(TaskDeadlines::<T>::get(&task_id).bidding_close.is_some(), Error::<T>::InvalidDeadline);
+        Ok(())
+    }
+
+    /// 选出报价最低的合格投标人，报价相同时取最早投标的 升级前没有投标方案的投标按任务奖励全额报价
+    pub(crate) fn lowest_qualified_bid(task_id: u128, requirement: &ReputationRequirement) -> Option<(T::AccountId, Balance)> {
+        let current_block = <frame_system::Module<T>>::block_number();
+        let reward = RelCreateTaskStake::get(&task_id);
+
+        RelTaskBidder::<T>::get(&task_id)
+            .into_iter()
+            .filter(|bidder| Reputations::<T>::get(bidder).meets(requirement))
+            .filter_map(|bidder| {
+                let price = match BidProposals::<T>::get(&task_id, &bidder) {
+                    Some(proposal) if proposal.delivery > current_block => proposal.price,
+                    Some(_) => return None,
+                    None => reward,
+                };
+                let sequence = BidSequence::<T>::get(&task_id, &bidder);
+                Some((price, sequence, bidder))
+            })
+            .min_by_key(|(price, sequence, _)| (*price, *sequence))
+            .map(|(price, _, bidder)| (bidder, price))
+    }
+
+    /// 自动选标 返回是否已选出中标人；不是自动选标的任务或没有合格投标时返回false
+    pub(crate) fn auto_award(task_id: u128, owner: &T::AccountId) -> Result<bool, DispatchError> {
+        let requirement = match AutoAward::get(&task_id) {
+            Some(requirement) => requirement,
+            None => return Ok(false),
+        };
+        let (winner, price) = match Self::lowest_qualified_bid(task_id, &requirement) {
+            Some(bid) => bid,
+            None => return Ok(false),
+        };
+
+        Self::do_delegate(task_id, owner, sp_std::vec![(winner.clone(), Permill::one())])?;
+        Self::deposit_event(RawEvent::TaskAutoAwarded(winner, task_id, price));
+
+        Ok(true)
+    }
+}
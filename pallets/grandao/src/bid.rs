@@ -0,0 +1,164 @@
+// 投标方案
+// 投标时除投标质押外还需提交投标方案：报价（不超过任务奖励）、预计交付区块与方案内容的哈希，
+// 完整的方案内容存储在链下。
+// 选标时中标方案成为任务的约定条款：任务奖励调整为中标报价，差额退还给任务创建者；
+// 交付截止区块调整为中标方案的预计交付区块；设置了里程碑的任务按原比例重新拆分奖励。
+// 选标前投标人可以修改投标质押与投标方案，或撤回投标；撤回时按BidWithdrawalFee扣除手续费转入国库。
+// 密封投标的任务投标时只提交报价哈希，投标方案在揭示阶段提交（见sealed.rs）
+
+use super::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// 投标方案
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BidProposal<BlockNumber, Hash> {
+    /// 报价 不超过任务奖励
+    pub price: Balance,
+    /// 预计交付区块
+    pub delivery: BlockNumber,
+    /// 方案内容的哈希
+    pub proposal_hash: Hash,
+}
+
+pub type BidProposalOf<T> = BidProposal<<T as frame_system::Config>::BlockNumber, <T as frame_system::Config>::Hash>;
+
+impl<T: Config> Module<T> {
+    /// 检查能否投标：不能投标自己的任务，任务在投标中，未重复投标，任务的投标人数量与持有的投标数量未超出限制，
+    /// 投标质押满足要求，且满足任务的投标策略
+    pub(crate) fn ensure_can_bid(task_id: u128, bidder: &T::AccountId, stake_token: Balance) -> dispatch::DispatchResult {
+        // 检查当前操作者是否为任务的创建者 不能自己投标自己
+        ensure!(*bidder != TaskDetail::<T>::get(&task_id).owner, Error::<T>::NotBidSelf);
+
+        // 检查任务是否存在
+        ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+
+        // 检查质押token是否满足要求：任务创建者质押数量的1/10
+        Self::ensure_valid_bid_stake(task_id, stake_token)?;
+
+        // 检查任务是否在投标中且未超过投标截止时间
+        Self::ensure_bidding_open(task_id)?;
+
+        // 检查是否已经投标过了
+        ensure!(!RelBidTaskStake::<T>::contains_key(&task_id, bidder), Error::<T>::NoDuplicateBid);
+
+        // 检查任务的投标人数量
+        ensure!((RelTaskBidder::<T>::get(&task_id).len() as u32) < T::MaxBiddersPerTask::get(), Error::<T>::TooManyBidders);
+
+        // 检查持有的投标数量
+        ensure!(ActiveBidCount::<T>::get(bidder) < T::MaxActiveBidsPerAccount::get(), Error::<T>::TooManyActiveBids);
+
+        // 检查是否满足任务的投标策略
+        Self::ensure_bid_permitted(task_id, bidder)
+    }
+
+    /// 质押（锁定）投标token，并保存投标质押记录、投标顺序、任务与投标人关系及我投标的任务列表
+    pub(crate) fn insert_bid(task_id: u128, bidder: &T::AccountId, stake_token: Balance) -> dispatch::DispatchResult {
+        // 检查可用余额是否足够质押 投标质押与任务奖励使用相同币种
+        let currency_id = Self::task_currency_id(task_id);
+        ensure!(T::MultiCurrency::can_reserve(currency_id, bidder, stake_token), Error::<T>::InsufficientBalance);
+        T::MultiCurrency::reserve(currency_id, bidder, stake_token)?;
+
+        RelBidTaskStake::<T>::insert(&task_id, bidder, stake_token);
+        Self::touch_bid_sequence(task_id, bidder);
+        ActiveBidCount::<T>::mutate(bidder, |count| *count += 1);
+        RelTaskBidder::<T>::mutate(&task_id, |bidders| bidders.insert(bidder.clone()));
+        BidTasks::<T>::insert(bidder, task_id, ());
+
+        Ok(())
+    }
+
+    /// 记录投标顺序 投标或修改投标时分配新的顺序号，自动选标报价相同时顺序号小者中标
+    pub(crate) fn touch_bid_sequence(task_id: u128, bidder: &T::AccountId) {
+        BidSequence::<T>::insert(&task_id, bidder, NextBidSequence::mutate(|seq| {
+            *seq = seq.wrapping_add(1);
+            *seq
+        }));
+    }
+
+    /// 检查任务是否在投标中且未超过投标截止时间
+    pub(crate) fn ensure_bidding_open(task_id: u128) -> dispatch::DispatchResult {
+        ensure!(Some(TaskStatus::Bidding) == RelTaskStatus::get(&task_id), Error::<T>::BidClosed);
+        if let Some(bidding_close) = TaskDeadlines::<T>::get(&task_id).bidding_close {
+            ensure!(<frame_system::Module<T>>::block_number() < bidding_close, Error::<T>::BidClosed);
+        }
+        Ok(())
+    }
+
+    /// 检查投标质押：少于任务创建者质押数量的1/10
+    pub(crate) fn ensure_valid_bid_stake(task_id: u128, stake_token: Balance) -> dispatch::DispatchResult {
+        let create_task_stake = RelCreateTaskStake::get(&task_id);
+        ensure!(stake_token < config::BID_STAKE_RATIO.mul_floor(create_task_stake), Error::<T>::InvalidStakeToken);
+        Ok(())
+    }
+
+    /// 检查投标方案：报价大于0且不超过任务奖励，预计交付区块晚于当前区块且不晚于任务的交付截止区块
+    pub(crate) fn ensure_valid_proposal(task_id: u128, proposal: &BidProposalOf<T>) -> dispatch::DispatchResult {
+        ensure!(proposal.price > 0, Error::<T>::InvalidBidPrice);
+        ensure!(proposal.price <= RelCreateTaskStake::get(&task_id), Error::<T>::InvalidBidPrice);
+
+        ensure!(proposal.delivery > <frame_system::Module<T>>::block_number(), Error::<T>::InvalidBidDelivery);
+        if let Some(delivery) = TaskDeadlines::<T>::get(&task_id).delivery {
+            ensure!(proposal.delivery <= delivery, Error::<T>::InvalidBidDelivery);
+        }
+
+        Ok(())
+    }
+
+    /// 将中标方案作为任务的约定条款
+    /// 退还任务奖励与中标报价的差额，调整交付截止区块，并按原比例重新拆分里程碑奖励
+    /// 升级前的投标没有投标方案，保持任务原有条款
+    /// 返回退还给任务创建者的token数量
+    pub(crate) fn bind_terms(task_id: u128, owner: &T::AccountId, receiver: &T::AccountId) -> Result<Balance, DispatchError> {
+        let proposal = match BidProposals::<T>::get(&task_id, receiver) {
+            Some(proposal) => proposal,
+            None => return Ok(0),
+        };
+        // 预计交付区块已过的方案不能中标
+        ensure!(proposal.delivery > <frame_system::Module<T>>::block_number(), Error::<T>::InvalidBidDelivery);
+
+        // 调整任务奖励 退还差额
+        let reward = RelCreateTaskStake::get(&task_id);
+        let refund = reward.checked_sub(proposal.price).ok_or(Error::<T>::InvalidBidPrice)?;
+        if refund > 0 {
+            Self::refund_escrow(task_id, owner, refund)?;
+            RelCreateTaskStake::insert(&task_id, proposal.price);
+
+            if TaskMilestones::contains_key(&task_id) {
+                let shares: Vec<Permill> = TaskMilestones::get(&task_id).iter().map(|m| m.share).collect();
+                TaskMilestones::insert(&task_id, Self::split_milestones(proposal.price, &shares));
+            }
+        }
+
+        // 调整交付截止区块
+        Self::reschedule_delivery(task_id, proposal.delivery);
+
+        Self::deposit_event(RawEvent::TaskTermsBound(receiver.clone(), task_id, proposal.price, proposal.delivery, refund));
+
+        Ok(refund)
+    }
+
+    /// 撤回投标：按`fee_ratio`扣除手续费转入国库，退还其余投标质押，并清除投标质押记录、投标方案与我投标的任务列表
+    /// 返回 (退还的token数量, 手续费)
+    pub(crate) fn withdraw_bid_stake(task_id: u128, bidder: &T::AccountId, fee_ratio: Permill) -> Result<(Balance, Balance), DispatchError> {
+        let currency_id = Self::task_currency_id(task_id);
+        let stake_token = Self::take_bid_stake(task_id, bidder);
+
+        let fee = fee_ratio * stake_token;
+        // 国库账户可能尚不存在，先罚没手续费再存入国库
+        if fee > 0 {
+            let unpaid = T::MultiCurrency::slash_reserved(currency_id, bidder, fee);
+            ensure!(unpaid == 0, Error::<T>::InsufficientBalance);
+            T::MultiCurrency::deposit(currency_id, &T::TreasuryAccount::get(), fee)?;
+        }
+        let refunded = stake_token - fee;
+        T::MultiCurrency::unreserve(currency_id, bidder, refunded);
+
+        BidTasks::<T>::remove(bidder, task_id);
+        BidProposals::<T>::remove(&task_id, bidder);
+        BidCommitments::<T>::remove(&task_id, bidder);
+
+        Ok((refunded, fee))
+    }
+}
@@ -0,0 +1,58 @@
+// 任务分类与标签
+// 任务分类由治理（CategoryOrigin）统一管理，创建任务时可以选择一个分类，并附带不超过MaxTagsPerTask个标签。
+// 分类与标签分别建立到任务ID的索引，供任务查询RPC按分类、标签筛选任务。
+// 标签按字节保存，计入任务的存储押金；被移除的分类不能再用于新任务，已有任务的索引保留
+
+use super::*;
+
+impl<T: Config> Module<T> {
+    /// 检查分类名称：不能为空且长度不超过MaxTagLength
+    pub(crate) fn ensure_valid_category_name(name: &[u8]) -> dispatch::DispatchResult {
+        ensure!(!name.is_empty(), Error::<T>::InvalidCategoryName);
+        ensure!(name.len() as u32 <= T::MaxTagLength::get(), Error::<T>::InvalidCategoryName);
+        Ok(())
+    }
+
+    /// 检查任务分类与标签，并返回标签需要缴纳的存储押金
+    /// 标签数量不超过MaxTagsPerTask，每个标签不能为空、长度不超过MaxTagLength且不能重复
+    pub(crate) fn ensure_valid_labels(category: Option<u32>, tags: &[Vec<u8>]) -> Result<Balance, DispatchError> {
+        if let Some(category) = category {
+            ensure!(Categories::contains_key(category), Error::<T>::NoSuchCategory);
+        }
+
+        ensure!(tags.len() as u32 <= T::MaxTagsPerTask::get(), Error::<T>::TooManyTags);
+        let mut seen = BTreeSet::new();
+        let mut bytes: usize = 0;
+        for tag in tags.iter() {
+            ensure!(!tag.is_empty() && tag.len() as u32 <= T::MaxTagLength::get(), Error::<T>::InvalidTag);
+            ensure!(seen.insert(tag), Error::<T>::InvalidTag);
+            bytes += tag.len();
+        }
+
+        Ok(T::DepositPerByte::get().saturating_mul(bytes as Balance))
+    }
+
+    /// 保存任务分类与标签，并更新索引
+    pub(crate) fn insert_labels(task_id: u128, category: Option<u32>, tags: Vec<Vec<u8>>) {
+        if let Some(category) = category {
+            TaskCategory::insert(&task_id, category);
+            TasksByCategory::insert(category, task_id, ());
+        }
+        if !tags.is_empty() {
+            for tag in tags.iter() {
+                TasksByTag::insert(tag, task_id, ());
+            }
+            TaskTags::insert(&task_id, tags);
+        }
+    }
+
+    /// 清除任务分类与标签及其索引
+    pub(crate) fn remove_labels(task_id: u128) {
+        if let Some(category) = TaskCategory::take(&task_id) {
+            TasksByCategory::remove(category, task_id);
+        }
+        for tag in TaskTags::take(&task_id).iter() {
+            TasksByTag::remove(tag, task_id);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 
-pub const BID_STAKE_RATIO: f32 = 0.1; //投标任务时的质押比率
-pub const TASK_DETAIL_MAX_BYTES: u128 = 2_000_000; //任务详情最大容量 2M
+use sp_runtime::Permill;
+
+pub const BID_STAKE_RATIO: Permill = Permill::from_parts(100_000); //投标任务时的质押比率 10%
 
 
@@ -0,0 +1,52 @@
+// 任务完成凭证
+// 任务完成（正常结算、自动验收、最后一个里程碑结清或仲裁中标者未败诉）时，为中标者铸造一个不可转让的凭证NFT，
+// 凭证记录任务ID、任务创建者与交付物哈希，按任务分类铸造到对应的NFT class中。
+// 铸造通过CredentialMinter完成，由运行时接入具体的NFT模块；铸造不需要用户签名，也不向中标者收取押金。
+// 凭证铸造失败不影响任务结算
+
+use super::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// 任务完成凭证
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TaskCredential<AccountId, Hash> {
+    /// 任务ID
+    pub task_id: u128,
+    /// 任务创建者
+    pub owner: AccountId,
+    /// 交付物哈希 中标者未提交交付物时为默认值
+    pub deliverable_hash: Hash,
+}
+
+pub type TaskCredentialOf<T> = TaskCredential<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::Hash>;
+
+/// 铸造任务完成凭证
+pub trait CredentialMinter<AccountId, Hash> {
+    /// 为中标者铸造凭证 `category`为任务分类，未设置分类的任务为None
+    fn mint_credential(receiver: &AccountId, category: Option<u32>, credential: TaskCredential<AccountId, Hash>) -> dispatch::DispatchResult;
+}
+
+/// 不铸造凭证
+impl<AccountId, Hash> CredentialMinter<AccountId, Hash> for () {
+    fn mint_credential(_: &AccountId, _: Option<u32>, _: TaskCredential<AccountId, Hash>) -> dispatch::DispatchResult {
+        Ok(())
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// 为完成任务的中标者铸造凭证
+    pub(crate) fn issue_credential(task_id: u128, owner: &T::AccountId, receiver: &T::AccountId) {
+        let credential = TaskCredential {
+            task_id,
+            owner: owner.clone(),
+            deliverable_hash: TaskDeliverables::<T>::get(&task_id).unwrap_or_default(),
+        };
+
+        match T::Credentials::mint_credential(receiver, TaskCategory::get(&task_id), credential) {
+            Ok(()) => Self::deposit_event(RawEvent::CredentialIssued(receiver.clone(), task_id)),
+            Err(e) => frame_support::debug::warn!("grandao: failed to issue credential of task {}: {:?}", task_id, e),
+        }
+    }
+}
@@ -0,0 +1,272 @@
+// 任务截止时间
+// 任务创建时可以设置投标截止区块、交付截止区块与验收期，截止事件按区块高度放入队列，
+// 由on_initialize按区块依次处理，每个区块处理的数量受MaxDeadlinesPerBlock限制，
+// 未处理完的留到下一个区块继续处理；处理失败的截止事件放回队列，在之后的区块重试，
+// 重试MaxDeadlineRetries次仍失败的截止事件从队列中移除，由任务双方手动处理。
+// 每个任务的投标人数量受MaxBiddersPerTask限制，处理截止事件的权重按投标人数量计算。
+// 验收期从任务交付时开始计算，未设置时使用默认的ReviewPeriod
+
+use super::*;
+use sp_runtime::traits::{One, Saturating, Zero};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// 任务截止时间
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TaskDeadline<BlockNumber> {
+    /// 投标截止区块 到达该区块时仍未选标的任务自动撤销
+    pub bidding_close: Option<BlockNumber>,
+    /// 交付截止区块 到达该区块时仍未交付的任务视为中标者违约
+    pub delivery: Option<BlockNumber>,
+    /// 验收期（区块数） 为空时使用默认的ReviewPeriod
+    pub review_window: Option<BlockNumber>,
+}
+
+/// 截止事件类型
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum DeadlineKind {
+    BiddingClose,   //投标截止
+    Delivery,       //交付截止
+    Review,         //验收截止
+    RevealClose,    //密封投标揭示截止
+}
+
+impl<T: Config> Module<T> {
+    /// 检查任务截止时间的有效性
+    pub(crate) fn ensure_valid_deadline(deadline: &TaskDeadline<T::BlockNumber>) -> dispatch::DispatchResult {
+        let current_block = <frame_system::Module<T>>::block_number();
+
+        if let Some(bidding_close) = deadline.bidding_close {
+            ensure!(bidding_close > current_block, Error::<T>::InvalidDeadline);
+        }
+        if let Some(delivery) = deadline.delivery {
+            ensure!(delivery > current_block, Error::<T>::InvalidDeadline);
+            if let Some(bidding_close) = deadline.bidding_close {
+                ensure!(delivery > bidding_close, Error::<T>::InvalidDeadline);
+            }
+        }
+        if let Some(review_window) = deadline.review_window {
+            ensure!(!review_window.is_zero(), Error::<T>::InvalidDeadline);
+        }
+
+        Ok(())
+    }
+
+    /// 保存任务截止时间，并将截止事件放入队列
+    pub(crate) fn schedule_deadlines(task_id: u128, deadline: TaskDeadline<T::BlockNumber>) {
+        if let Some(bidding_close) = deadline.bidding_close {
+            DeadlineQueue::<T>::insert(bidding_close, task_id, DeadlineKind::BiddingClose);
+        }
+        if let Some(delivery) = deadline.delivery {
+            DeadlineQueue::<T>::insert(delivery, task_id, DeadlineKind::Delivery);
+        }
+        TaskDeadlines::<T>::insert(&task_id, deadline);
+    }
+
+    /// 清除任务截止时间及队列中的截止事件
+    pub(crate) fn clear_deadlines(task_id: u128) {
+        let deadline = TaskDeadlines::<T>::take(&task_id);
+        if let Some(bidding_close) = deadline.bidding_close {
+            DeadlineQueue::<T>::remove(bidding_close, task_id);
+        }
+        if let Some(delivery) = deadline.delivery {
+            DeadlineQueue::<T>::remove(delivery, task_id);
+        }
+        Self::schedule_reveal(task_id, None);
+        Self::cancel_review(task_id);
+        DeadlineRetries::remove_prefix(&task_id);
+    }
+
+    /// 调整任务的交付截止区块，并更新队列中的截止事件
+    pub(crate) fn reschedule_delivery(task_id: u128, delivery: T::BlockNumber) {
+        TaskDeadlines::<T>::mutate(&task_id, |deadline| {
+            if let Some(old) = deadline.delivery.replace(delivery) {
+                DeadlineQueue::<T>::remove(old, task_id);
+            }
+        });
+        DeadlineQueue::<T>::insert(delivery, task_id, DeadlineKind::Delivery);
+    }
+
+    /// 交付截止区块顺延一个验收期（从当前区块开始计算）
+    fn postpone_delivery(task_id: u128) {
+        let review_window = TaskDeadlines::<T>::get(&task_id).review_window
+            .unwrap_or_else(T::ReviewPeriod::get);
+        let delivery = <frame_system::Module<T>>::block_number().saturating_add(review_window);
+        Self::reschedule_delivery(task_id, delivery);
+    }
+
+    /// 任务交付后开始计算验收期，并将验收截止事件放入队列
+    pub(crate) fn schedule_review(task_id: u128) {
+        let review_window = TaskDeadlines::<T>::get(&task_id).review_window
+            .unwrap_or_else(T::ReviewPeriod::get);
+        let review_close = <frame_system::Module<T>>::block_number().saturating_add(review_window);
+
+        DeadlineQueue::<T>::insert(review_close, task_id, DeadlineKind::Review);
+        ReviewDeadline::<T>::insert(&task_id, review_close);
+    }
+
+    /// 取消验收截止事件（创建者已验收或已发起仲裁）
+    pub(crate) fn cancel_review(task_id: u128) {
+        if let Some(review_close) = ReviewDeadline::<T>::take(&task_id) {
+            DeadlineQueue::<T>::remove(review_close, task_id);
+        }
+    }
+
+    /// 处理已到期的截止事件 每个区块最多处理MaxDeadlinesPerBlock个
+    pub(crate) fn process_deadlines(now: T::BlockNumber) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let mut weight: Weight = db_weight.reads(1);
+
+        let mut head = DeadlineQueueHead::<T>::get();
+        if head.is_zero() {
+            head = now;
+        }
+
+        let mut budget = T::MaxDeadlinesPerBlock::get();
+        while head <= now && budget > 0 {
+            let due: Vec<(u128, DeadlineKind)> = DeadlineQueue::<T>::iter_prefix(head)
+                .take(budget as usize)
+                .collect();
+            weight = weight.saturating_add(db_weight.reads(1));
+
+            if due.is_empty() {
+                head = head.saturating_add(One::one());
+                budget -= 1;
+                continue;
+            }
+
+            for (task_id, kind) in due {
+                let bidders = RelTaskBidder::<T>::get(&task_id).len() as Weight;
+                DeadlineQueue::<T>::remove(head, task_id);
+                match Self::expire_task(task_id, kind) {
+                    Ok(()) => DeadlineRetries::remove(task_id, kind),
+                    Err(e) => {
+                        frame_support::debug::warn!("grandao: failed to process deadline of task {}: {:?}", task_id, e);
+                        Self::requeue_deadline(task_id, kind, now);
+                    },
+                }
+                weight = weight
+                    .saturating_add(db_weight.reads_writes(8, 10))
+                    .saturating_add(db_weight.reads_writes(3, 5).saturating_mul(bidders));
+                budget -= 1;
+            }
+        }
+
+        DeadlineQueueHead::<T>::put(head);
+        weight.saturating_add(db_weight.writes(1))
+    }
+
+    /// 将处理失败的截止事件放回队列，在下一个空闲的区块重试
+    /// 验收截止同时更新ReviewDeadline，保证取消验收时能找到重试的截止事件；
+    /// 已重试MaxDeadlineRetries次的截止事件不再放回队列
+    fn requeue_deadline(task_id: u128, kind: DeadlineKind, now: T::BlockNumber) {
+        let retries = DeadlineRetries::mutate(task_id, kind, |retries| {
+            *retries = retries.saturating_add(1);
+            *retries
+        });
+        if retries > T::MaxDeadlineRetries::get() {
+            DeadlineRetries::remove(task_id, kind);
+            if kind == DeadlineKind::Review {
+                ReviewDeadline::<T>::remove(&task_id);
+            }
+            Self::deposit_event(RawEvent::DeadlineDropped(task_id, kind));
+            return;
+        }
+
+        let mut retry_at = now.saturating_add(One::one());
+        while DeadlineQueue::<T>::contains_key(retry_at, task_id) {
+            retry_at = retry_at.saturating_add(One::one());
+        }
+        DeadlineQueue::<T>::insert(retry_at, task_id, kind);
+        if kind == DeadlineKind::Review {
+            ReviewDeadline::<T>::insert(&task_id, retry_at);
+        }
+    }
+
+    /// 处理单个截止事件
+    /// - 投标截止：仍在投标中的任务撤销，退还创建者与所有投标人的质押；密封投标的任务进入揭示阶段
+    /// - 揭示截止：罚没未揭示的密封投标，没有有效投标的任务撤销
+    /// - 自动选标的任务在投标截止（密封投标为揭示截止）时选出报价最低的合格投标人，没有合格投标时撤销
+    /// - 交付截止：仍在进行中的任务视为中标者违约，其投标质押罚没给创建者，任务奖励退还创建者（子任务退回父任务）；
+    ///   里程碑已交付等待验收或任务在仲裁中时不视为违约，交付截止顺延一个验收期
+    /// - 验收截止：仍处于已交付的任务自动验收，结算任务奖励并退还中标者的投标质押；
+    ///   已交付的里程碑自动验收，只支付该里程碑的奖励
+    #[transactional]
+    fn expire_task(task_id: u128, kind: DeadlineKind) -> dispatch::DispatchResult {
+        let task_status = match RelTaskStatus::get(&task_id) {
+            Some(status) => status,
+            None => return Ok(()),
+        };
+        let owner = TaskDetail::<T>::get(&task_id).owner;
+
+        match (kind, task_status) {
+            (DeadlineKind::BiddingClose, TaskStatus::Bidding) if Self::is_sealed(task_id) => return Ok(()),
+            (DeadlineKind::BiddingClose, TaskStatus::Bidding) => {
+                if Self::auto_award(task_id, &owner)? {
+                    return Ok(());
+                }
+                Self::release_stakes(task_id, &owner)?;
+            },
+            (DeadlineKind::RevealClose, TaskStatus::Bidding) => {
+                Self::forfeit_unrevealed(task_id)?;
+                if AutoAward::contains_key(&task_id) {
+                    if Self::auto_award(task_id, &owner)? {
+                        return Ok(());
+                    }
+                } else if !RelTaskBidder::<T>::get(&task_id).is_empty() {
+                    return Ok(());
+                }
+                Self::release_stakes(task_id, &owner)?;
+            },
+            (DeadlineKind::Delivery, TaskStatus::Doing) => {
+                // 已交付、等待验收的里程碑不视为违约
+                if let Some((_, milestone)) = Self::current_milestone(task_id) {
+                    if milestone.status == MilestoneStatus::Delivered {
+                        Self::postpone_delivery(task_id);
+                        return Ok(());
+                    }
+                }
+
+                // 罚没所有中标团队成员的投标质押
+                for (receiver, bid_stake) in Self::slash_team_stakes(task_id, &owner)? {
+                    Self::update_reputation(&receiver, |r| r.abandoned = r.abandoned.saturating_add(1));
+                    Self::deposit_event(RawEvent::ReceiverDefaulted(receiver, task_id, bid_stake));
+                }
+
+                let create_stake = RelCreateTaskStake::take(&task_id);
+                Self::refund_escrow(task_id, &owner, create_stake)?;
+            },
+            (DeadlineKind::Delivery, TaskStatus::Arbitrating) | (DeadlineKind::Delivery, TaskStatus::Judging) => {
+                // 仲裁结束后回到进行中且仍未交付的，在顺延后的交付截止时按违约处理
+                Self::postpone_delivery(task_id);
+                return Ok(());
+            },
+            (DeadlineKind::Review, TaskStatus::Delivered) => {
+                ReviewDeadline::<T>::remove(&task_id);
+                let receiver = RelTaskReceiver::<T>::get(&task_id);
+                Self::settle_task(task_id, &owner)?;
+
+                Self::set_task_status(task_id, TaskStatus::Finished);
+                Self::deposit_event(RawEvent::TaskAutoAccepted(owner, receiver, task_id));
+                return Ok(());
+            },
+            (DeadlineKind::Review, TaskStatus::Doing) => {
+                ReviewDeadline::<T>::remove(&task_id);
+                if let Some((index, milestone)) = Self::current_milestone(task_id) {
+                    if milestone.status == MilestoneStatus::Delivered {
+                        Self::pay_milestone(task_id, index, &owner)?;
+                        Self::deposit_event(RawEvent::MilestoneAutoAccepted(task_id, index));
+                    }
+                }
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+
+        Self::set_task_status(task_id, TaskStatus::UnDone);
+        Self::deposit_event(RawEvent::TaskExpired(task_id, TaskStatus::UnDone));
+
+        Ok(())
+    }
+}
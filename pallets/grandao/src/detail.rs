@@ -0,0 +1,60 @@
+// 任务详情
+// 链上只保存任务标题、任务内容的哈希与链下存储地址（IPFS CID），完整的任务内容存储在链下。
+// 标题与地址的最大长度可配置，创建任务时按保存的字节数缴纳存储押金，任务被移除时退还
+
+use super::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// 任务详情
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TaskInfo<AccountId, Hash, BlockNumber> {
+    /// 任务创建者
+    pub owner: AccountId,
+    /// 任务标题
+    pub title: Vec<u8>,
+    /// 任务内容的哈希
+    pub content_hash: Hash,
+    /// 任务内容的链下存储地址 IPFS CID 或 ipfs://CID
+    pub uri: Vec<u8>,
+    /// 创建任务的区块高度
+    pub created_at: BlockNumber,
+    /// 存储押金
+    pub deposit: Balance,
+}
+
+pub type TaskInfoOf<T> = TaskInfo<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::Hash, <T as frame_system::Config>::BlockNumber>;
+
+const IPFS_SCHEME: &[u8] = b"ipfs://";
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// 检查是否为IPFS CID格式的地址
+/// - CIDv0：以"Qm"开头的46位base58字符串
+/// - CIDv1：以"b"开头的base32（小写）字符串
+/// 允许带"ipfs://"前缀
+pub fn is_valid_cid(uri: &[u8]) -> bool {
+    let cid = if uri.starts_with(IPFS_SCHEME) { &uri[IPFS_SCHEME.len()..] } else { uri };
+
+    if cid.len() == 46 && cid.starts_with(b"Qm") {
+        return cid.iter().all(|c| BASE58_ALPHABET.contains(c));
+    }
+    if cid.len() > 1 && cid[0] == b'b' {
+        return cid[1..].iter().all(|c| matches!(c, b'a'..=b'z' | b'2'..=b'7'));
+    }
+
+    false
+}
+
+impl<T: Config> Module<T> {
+    /// 检查任务标题与地址，并返回需要缴纳的存储押金
+    pub(crate) fn ensure_valid_detail(title: &[u8], uri: &[u8]) -> Result<Balance, DispatchError> {
+        ensure!(!title.is_empty(), Error::<T>::InvalidTaskDetail);
+        ensure!(title.len() as u32 <= T::MaxTitleLength::get(), Error::<T>::TitleTooLong);
+        ensure!(uri.len() as u32 <= T::MaxUriLength::get(), Error::<T>::UriTooLong);
+        ensure!(is_valid_cid(uri), Error::<T>::InvalidTaskUri);
+
+        let bytes = (title.len() + uri.len()) as Balance;
+        Ok(T::DepositPerByte::get().saturating_mul(bytes))
+    }
+}
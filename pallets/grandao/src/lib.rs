@@ -1,11 +1,16 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+#![recursion_limit = "256"]
 
 /// Edit this file to define custom logic or remove it if it is not needed.
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// https://substrate.dev/docs/en/knowledgebase/runtime/frame
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, ensure, dispatch, StorageMap, StorageDoubleMap };
+use codec::{Encode, Decode};
+use frame_support::{decl_module, decl_storage, decl_event, decl_error, ensure, dispatch, transactional, StorageMap, StorageDoubleMap,
+    StorageValue, IterableStorageMap, IterableStorageDoubleMap, traits::{BalanceStatus, EnsureOrigin, Get, Randomness}, weights::Weight };
 use frame_system::ensure_signed;
+use orml_traits::{BasicCurrency, BasicReservableCurrency, MultiCurrency, MultiReservableCurrency};
+use sp_runtime::{DispatchError, RuntimeDebug, Permill};
 
 use sp_std::vec::Vec; 
 use sp_std::collections::btree_set::BTreeSet;
@@ -17,25 +22,156 @@ mod mock;
 mod tests;
 
 mod config;
+mod migrations;
+mod status;
+mod arbitration;
+mod deadline;
+mod milestone;
+mod detail;
+mod query;
+mod reputation;
+mod policy;
+mod bid;
+mod category;
+mod credential;
+mod team;
+mod subtask;
+mod sealed;
+mod auction;
+
+pub use status::{TaskStatus, StatusActor};
+pub use arbitration::{Dispute, DisputeOf};
+pub use deadline::{TaskDeadline, DeadlineKind};
+pub use milestone::{Milestone, MilestoneStatus};
+pub use detail::{TaskInfo, TaskInfoOf, is_valid_cid};
+pub use query::{TaskView, TaskViewOf, MAX_PAGE_SIZE};
+pub use reputation::{Reputation, ReputationRequirement, MAX_RATING};
+pub use bid::{BidProposal, BidProposalOf};
+pub use credential::{TaskCredential, TaskCredentialOf, CredentialMinter};
+pub use policy::{BidPolicy, BidPolicyOf, ClassHolder};
+
+pub type Balance = u128;
+pub type CurrencyIdOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+
+// 存储版本
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+    V1, //任务状态以u8存储
+    V2, //任务状态以TaskStatus存储
+    V3, //任务详情以TaskInfo存储（标题、内容哈希、链下地址）
+    V4, //任务ID由NextTaskId分配，调用方的任务ID作为客户端引用
+    V5, //任务列表、用户列表与我的任务列表由BTreeSet改为按键索引的map/double_map
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1
+    }
+}
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Config: frame_system::Config {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-}
 
-// 任务状态
-// 任务状态的更新默认只有任务创建者才有权限
-// 但其中Delivered只有任务中标者有权限操作，Arbitrating、Judging两个状态双方都有权限操作
-pub enum TaskStatus {
-    Bidding     = 1, //待认领（投标中）
-    Doing       = 2, //进行中
-    UnDone      = 3, //已撤销    
-    Delivered   = 4, //已交付
-    Accepted    = 5, //已验收
-    Arbitrating = 6, //仲裁中
-    Judging     = 7, //审判中
-    Finished    = 8, //已结束
+	/// 原生货币，用于仲裁保证金、仲裁员质押、上诉费与存储押金
+	type Currency: BasicReservableCurrency<Self::AccountId, Balance = Balance>;
+
+	/// 任务奖励与投标质押所使用的多币种货币，创建任务与投标时通过reserve锁定质押
+	type MultiCurrency: MultiReservableCurrency<Self::AccountId, Balance = Balance>;
+
+	/// 原生货币的币种ID，未指定币种的任务使用原生货币
+	type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
+
+	/// 抽取仲裁小组所使用的随机数来源
+	type Randomness: Randomness<Self::Hash>;
+
+	/// 注册成为仲裁员所需的最低质押
+	type MinArbitratorStake: Get<Balance>;
+
+	/// 发起仲裁所需缴纳的保证金
+	type DisputeBond: Get<Balance>;
+
+	/// 每个争议案件的仲裁小组人数，建议为奇数
+	type ArbitratorPanelSize: Get<u32>;
+
+	/// 仲裁员池的最大人数
+	type MaxArbitrators: Get<u32>;
+
+	/// 每个争议案件最多可提交的证据数量
+	type MaxEvidence: Get<u32>;
+
+	/// 对仲裁裁决上诉后，给出最终判决的来源（理事会过半数）
+	type JudgeOrigin: EnsureOrigin<Self::Origin>;
+
+	/// 上诉所需缴纳的费用，上诉费转入国库
+	type AppealFee: Get<Balance>;
+
+	/// 仲裁裁决后的上诉期（区块数）
+	type AppealPeriod: Get<Self::BlockNumber>;
+
+	/// 仲裁小组的投票期（区块数），超过投票期仍未全部投票时以已投票的结果裁决
+	type RulingPeriod: Get<Self::BlockNumber>;
+
+	/// 国库账户，接收上诉费
+	type TreasuryAccount: Get<Self::AccountId>;
+
+	/// 每个区块最多处理的到期任务数量
+	type MaxDeadlinesPerBlock: Get<u32>;
+
+	/// 处理失败的截止事件最多重试的次数，超过后从队列中移除
+	type MaxDeadlineRetries: Get<u32>;
+
+	/// 默认验收期（区块数），交付后超过验收期未验收也未发起仲裁的任务自动验收
+	type ReviewPeriod: Get<Self::BlockNumber>;
+
+	/// 每个任务最多可设置的里程碑数量
+	type MaxMilestones: Get<u32>;
+
+	/// 任务标题的最大字节数
+	type MaxTitleLength: Get<u32>;
+
+	/// 任务链下存储地址的最大字节数
+	type MaxUriLength: Get<u32>;
+
+	/// 任务详情每字节需缴纳的存储押金
+	type DepositPerByte: Get<Balance>;
+
+	/// 每个账户最多同时进行中（未结束、未撤销）的创建任务数量
+	type MaxOpenTasksPerAccount: Get<u32>;
+
+	/// 每个账户最多同时持有的投标数量
+	type MaxActiveBidsPerAccount: Get<u32>;
+
+	/// 每个任务最多的投标人数量，限制处理截止事件时遍历投标人的开销
+	type MaxBiddersPerTask: Get<u32>;
+
+	/// 撤回投标时从投标质押中扣除的手续费比例，手续费转入国库；为0时不收取
+	type BidWithdrawalFee: Get<Permill>;
+
+	/// 管理任务分类的来源（理事会过半数）
+	type CategoryOrigin: EnsureOrigin<Self::Origin>;
+
+	/// 每个任务最多可附带的标签数量
+	type MaxTagsPerTask: Get<u32>;
+
+	/// 标签与分类名称的最大字节数
+	type MaxTagLength: Get<u32>;
+
+	/// 任务完成时为中标者铸造凭证NFT
+	type Credentials: CredentialMinter<Self::AccountId, Self::Hash>;
+
+	/// 团队任务最多的中标人数
+	type MaxTeamSize: Get<u32>;
+
+	/// 查询账户是否持有某NFT class的token，用于按NFT限制投标的任务
+	type NftHolders: ClassHolder<Self::AccountId>;
+
+	/// 投标白名单的最大账户数量
+	type MaxBidAllowlist: Get<u32>;
+
+	/// 密封投标揭示截止后仍未揭示的投标被罚没的投标质押比例，罚没部分转入国库
+	type UnrevealedBidPenalty: Get<Permill>;
 }
 
 // The pallet's runtime storage items.
@@ -47,34 +183,132 @@ decl_storage! {
 	trait Store for Module<T: Config> as GrandaoModule {
 
         /*  任务基本信息============================================================================ */
-        /// 任务列表 (TaskId, ...)
-        pub TaskList get(fn task_list): BTreeSet<u128>;
-        /// 任务详情 TaskId => (AccountId, TaskDetailJson, BlockNumber)
-		pub TaskDetail get(fn task_detail): map hasher(twox_64_concat) u128 => (T::AccountId, Vec<u8>, T::BlockNumber);
+        /// 按状态索引的任务列表 (TaskStatus, TaskId) => ()
+        pub TasksByStatus: double_map hasher(twox_64_concat) TaskStatus, hasher(twox_64_concat) u128 => ();
+        /// 下一个任务ID
+        pub NextTaskId get(fn next_task_id): u128 = 1;
+        /// 任务与客户端引用关系 TaskId => ClientRef
+        pub TaskClientRef get(fn task_client_ref): map hasher(twox_64_concat) u128 => Option<u128>;
+        /// 客户端引用与任务关系 (AccountId, ClientRef) => TaskId 同一创建者的客户端引用不能重复
+        pub TaskByClientRef get(fn task_by_client_ref): double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u128 => Option<u128>;
+        /// 任务与奖励币种关系 TaskId => CurrencyId 未记录的任务使用原生货币
+        pub TaskCurrency get(fn task_currency): map hasher(twox_64_concat) u128 => Option<CurrencyIdOf<T>>;
+        /// 任务详情 TaskId => TaskInfo
+		pub TaskDetail get(fn task_detail): map hasher(twox_64_concat) u128 => TaskInfoOf<T>;
         /// 任务总数 
         pub TaskCount get (fn task_count): u128;
 
         /*  任务关系信息============================================================================ */
         /// 任务与状态关系 TaskId => Status
-        pub RelTaskStatus get(fn rel_task_status): map hasher(twox_64_concat) u128 => u8;
+        pub RelTaskStatus get(fn rel_task_status): map hasher(twox_64_concat) u128 => Option<TaskStatus>;
         /// 创建任务与质押的Token数量关系 TaskId => Token
         pub RelCreateTaskStake get(fn rel_create_task_stake): map hasher(twox_64_concat) u128 => u128;
+        /// 升级前未实际锁定、迁移时也无法补充锁定的质押 (TaskId, AccountId) => Token 对应的质押记录已清零
+        pub UnbackedStakes get(fn unbacked_stake): double_map hasher(twox_64_concat) u128, hasher(blake2_128_concat) T::AccountId => Balance;
         /// 投标任务与质押的Token数量关系 (TaskId, AccountId) => Token
         pub RelBidTaskStake get(fn rel_bid_stake): double_map hasher(twox_64_concat) u128, hasher(blake2_128_concat) T::AccountId => u128;
+        /// 投标任务与投标方案关系 (TaskId, AccountId) => BidProposal 选标后只保留中标方案
+        pub BidProposals get(fn bid_proposal): double_map hasher(twox_64_concat) u128, hasher(blake2_128_concat) T::AccountId => Option<BidProposalOf<T>>;
+        /// 密封投标任务与揭示截止区块关系 TaskId => BlockNumber
+        pub SealedBidding get(fn sealed_bidding): map hasher(twox_64_concat) u128 => Option<T::BlockNumber>;
+        /// 密封投标尚未揭示的报价哈希 (TaskId, AccountId) => Hash 揭示后移除
+        pub BidCommitments get(fn bid_commitment): double_map hasher(twox_64_concat) u128, hasher(blake2_128_concat) T::AccountId => Option<T::Hash>;
+        /// 投标顺序 (TaskId, AccountId) => Sequence 自动选标时报价相同的先投标者中标
+        pub BidSequence get(fn bid_sequence): double_map hasher(twox_64_concat) u128, hasher(blake2_128_concat) T::AccountId => u64;
+        /// 下一个投标顺序号
+        pub NextBidSequence get(fn next_bid_sequence): u64;
+        /// 自动选标任务与中标所需的信誉要求关系 TaskId => ReputationRequirement
+        pub AutoAward get(fn auto_award_requirement): map hasher(twox_64_concat) u128 => Option<ReputationRequirement>;
         /// 任务与投标人关系 一对多 TaskId => (AccountId, ...)
         pub RelTaskBidder get(fn rel_task_bidder): map hasher(twox_64_concat) u128 => BTreeSet<T::AccountId>;
-        /// 任务与中标人关系 一对一 TaskId => AccountId
+        /// 任务与中标人关系 一对一 TaskId => AccountId 团队任务为团队负责人（第一个成员）
         pub RelTaskReceiver get(fn rel_task_receiver): map hasher(twox_64_concat) u128 => T::AccountId;
+        /// 团队任务与中标团队关系 TaskId => ((AccountId, Share), ...) 单人中标的任务不记录
+        pub TaskTeam get(fn task_team_shares): map hasher(twox_64_concat) u128 => Vec<(T::AccountId, Permill)>;
+        /// 任务与交付物哈希关系 TaskId => Hash
+        pub TaskDeliverables get(fn task_deliverable): map hasher(twox_64_concat) u128 => Option<T::Hash>;
+
+        /*  任务分类与标签============================================================================ */
+        /// 任务分类 CategoryId => Name
+        pub Categories get(fn category): map hasher(twox_64_concat) u32 => Option<Vec<u8>>;
+        /// 下一个分类ID
+        pub NextCategoryId get(fn next_category_id): u32;
+        /// 任务与分类关系 TaskId => CategoryId
+        pub TaskCategory get(fn task_category): map hasher(twox_64_concat) u128 => Option<u32>;
+        /// 任务与标签关系 TaskId => (Tag, ...)
+        pub TaskTags get(fn task_tags): map hasher(twox_64_concat) u128 => Vec<Vec<u8>>;
+        /// 按分类索引的任务列表 (CategoryId, TaskId) => ()
+        pub TasksByCategory: double_map hasher(twox_64_concat) u32, hasher(twox_64_concat) u128 => ();
+        /// 按标签索引的任务列表 (Tag, TaskId) => ()
+        pub TasksByTag: double_map hasher(blake2_128_concat) Vec<u8>, hasher(twox_64_concat) u128 => ();
+
+        /*  子任务============================================================================== */
+        /// 子任务与父任务关系 TaskId => ParentTaskId
+        pub ParentTask get(fn parent_task): map hasher(twox_64_concat) u128 => Option<u128>;
+        /// 父任务与子任务关系 (ParentTaskId, TaskId) => ()
+        pub SubTasks: double_map hasher(twox_64_concat) u128, hasher(twox_64_concat) u128 => ();
+        /// 父任务未结束、未撤销的子任务数量 ParentTaskId => u32
+        pub OpenSubTaskCount get(fn open_subtask_count): map hasher(twox_64_concat) u128 => u32;
 
         /*  用户关系信息============================================================================ */   
-        /// 会员列表 (AccountId, ...)
-        pub UserList get(fn user_list): BTreeSet<T::AccountId>;
-        /// 我创建的任务列表 AccountId => (TaskId, ...)
-        pub MyCreateTasks get (fn my_create_tasks): map hasher(blake2_128_concat) T::AccountId => BTreeSet<u128>;
-        /// 我投标的任务列表 AccountId => (TaskId, ...)
-        pub MyBidTasks get (fn my_bid_tasks): map hasher(blake2_128_concat) T::AccountId => BTreeSet<u128>;
-        /// 我中标的任务列表 AccountId => (TaskId, ...)
-        pub MyReceiveTasks get (fn my_receive_tasks): map hasher(blake2_128_concat) T::AccountId => BTreeSet<u128>;
+        /// 会员列表 AccountId => ()
+        pub Users get(fn users): map hasher(blake2_128_concat) T::AccountId => ();
+        /// 会员总数
+        pub UserCount get(fn user_count): u64;
+        /// 我创建的任务列表 (AccountId, TaskId) => ()
+        pub CreatedTasks get(fn created_tasks): double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u128 => ();
+        /// 我投标的任务列表 (AccountId, TaskId) => ()
+        pub BidTasks get(fn bid_tasks): double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u128 => ();
+        /// 我中标的任务列表 (AccountId, TaskId) => ()
+        pub ReceivedTasks get(fn received_tasks): double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u128 => ();
+        /// 我创建的进行中（未结束、未撤销）任务数量 AccountId => Count
+        pub OpenTaskCount get(fn open_task_count): map hasher(blake2_128_concat) T::AccountId => u32;
+        /// 我持有的投标数量 AccountId => Count
+        pub ActiveBidCount get(fn active_bid_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /*  信誉记录============================================================================== */
+        /// 账户的信誉记录 AccountId => Reputation
+        pub Reputations get(fn reputation): map hasher(blake2_128_concat) T::AccountId => Reputation;
+        /// 任务创建者对中标者的评分 TaskId => Rating
+        pub TaskRatings get(fn task_rating): map hasher(twox_64_concat) u128 => Option<u8>;
+        /// 任务的投标策略 TaskId => BidPolicy 未设置的任务为开放投标
+        pub TaskBidPolicy get(fn task_bid_policy): map hasher(twox_64_concat) u128 => BidPolicyOf<T>;
+
+        /*  任务里程碑============================================================================== */
+        /// 任务与里程碑关系 TaskId => (Milestone, ...) 按交付顺序排列，未设置里程碑的任务为空
+        pub TaskMilestones get(fn task_milestones): map hasher(twox_64_concat) u128 => Vec<Milestone>;
+        /// 发起仲裁的里程碑 TaskId => MilestoneIndex
+        pub DisputedMilestone get(fn disputed_milestone): map hasher(twox_64_concat) u128 => Option<u32>;
+
+        /*  任务截止时间============================================================================ */
+        /// 任务与截止时间关系 TaskId => TaskDeadline
+        pub TaskDeadlines get(fn task_deadlines): map hasher(twox_64_concat) u128 => TaskDeadline<T::BlockNumber>;
+        /// 截止事件队列 (BlockNumber, TaskId) => DeadlineKind
+        pub DeadlineQueue get(fn deadline_queue): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) u128 => Option<DeadlineKind>;
+        /// 任务与验收截止区块关系 TaskId => BlockNumber
+        pub ReviewDeadline get(fn review_deadline): map hasher(twox_64_concat) u128 => Option<T::BlockNumber>;
+        /// 截止事件队列中最早的未处理区块
+        pub DeadlineQueueHead get(fn deadline_queue_head): T::BlockNumber;
+        /// 截止事件已重试的次数 (TaskId, DeadlineKind) => u32
+        pub DeadlineRetries get(fn deadline_retries): double_map hasher(twox_64_concat) u128, hasher(twox_64_concat) DeadlineKind => u32;
+
+        /*  争议仲裁信息============================================================================ */
+        /// 仲裁员池 (AccountId, ...)
+        pub Arbitrators get(fn arbitrators): Vec<T::AccountId>;
+        /// 仲裁员质押的Token数量 AccountId => Token
+        pub ArbitratorStakes get(fn arbitrator_stakes): map hasher(blake2_128_concat) T::AccountId => Balance;
+        /// 仲裁员正在参与的未结案件数 AccountId => Count
+        pub ArbitratorCaseCount get(fn arbitrator_case_count): map hasher(blake2_128_concat) T::AccountId => u32;
+        /// 任务与争议案件关系 TaskId => Dispute
+        pub Disputes get(fn disputes): map hasher(twox_64_concat) u128 => Option<DisputeOf<T>>;
+        /// 仲裁员投票 (TaskId, AccountId) => 中标者应得的任务奖励比例
+        pub DisputeVotes get(fn dispute_votes): double_map hasher(twox_64_concat) u128, hasher(blake2_128_concat) T::AccountId => Option<Permill>;
+        /// 仲裁裁决 TaskId => (中标者应得的任务奖励比例, 上诉截止区块)
+        pub DisputeRulings get(fn dispute_rulings): map hasher(twox_64_concat) u128 => Option<(Permill, T::BlockNumber)>;
+
+        /*  存储版本============================================================================ */
+        /// 存储版本 新链创世时直接为最新版本
+        StorageVersion build(|_| Releases::V5): Releases;
 
 	}
 }
@@ -82,17 +316,102 @@ decl_storage! {
 // Pallets use events to inform users when important changes are made.
 // https://substrate.dev/docs/en/knowledgebase/runtime/events
 decl_event!(
-	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
-		/// 当任务被创建时触发此事件 [owner, task_id, task_status, stake_token, task_detail]
-        TaskCreated(AccountId, u128, u8, u128, Vec<u8>),
+	pub enum Event<T> where
+        AccountId = <T as frame_system::Config>::AccountId,
+        Hash = <T as frame_system::Config>::Hash,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
+        CurrencyId = CurrencyIdOf<T>,
+    {
+		/// 当任务被创建时触发此事件 [owner, task_id, task_status, currency_id, stake_token, content_hash, client_ref]
+        TaskCreated(AccountId, u128, TaskStatus, CurrencyId, u128, Hash, Option<u128>),
         /// 当任务状态被更新触发此事件 [owner|bidder, task_id, task_status]
-        TaskStatusUpdated(AccountId, u128, u8),
+        TaskStatusUpdated(AccountId, u128, TaskStatus),
         /// 当任务被撤销时触发此事件 [owner, task_id]
 		TaskRevoked(AccountId, u128),
         /// 当完成任务投标时触发此事件 [bidder, task_id, stake_token]
         BidCompleted(AccountId, u128, u128),
-        /// 当完成任务选（中）标时触发此事件 [owner, bidder, task_id, stake_token]
+        /// 当完成任务选（中）标时触发此事件 [owner, bidder, task_id] 团队任务为团队负责人
         TaskDelegated(AccountId, AccountId, u128),
+        /// 当未中标人的投标质押被退还时触发此事件 [bidder, task_id, stake_token]
+        BidRefunded(AccountId, u128, u128),
+        /// 当任务结束并完成结算时触发此事件 [owner, receiver, task_id, reward, bid_stake_returned] 团队任务为团队负责人与合计数量
+        TaskSettled(AccountId, AccountId, u128, u128, u128),
+        /// 当注册成为仲裁员时触发此事件 [arbitrator, stake_token]
+        ArbitratorRegistered(AccountId, u128),
+        /// 当仲裁员退出仲裁员池时触发此事件 [arbitrator, stake_token]
+        ArbitratorUnregistered(AccountId, u128),
+        /// 当发起仲裁时触发此事件 [plaintiff, task_id, panel]
+        DisputeOpened(AccountId, u128, Vec<AccountId>),
+        /// 当提交证据时触发此事件 [owner|receiver, task_id, evidence_hash]
+        EvidenceSubmitted(AccountId, u128, Hash),
+        /// 当仲裁员投票时触发此事件 [arbitrator, task_id, receiver_share]
+        RulingVoted(AccountId, u128, Permill),
+        /// 当仲裁裁决执行完成时触发此事件 [task_id, receiver_share, paid_to_receiver, refunded_to_owner]
+        DisputeResolved(u128, Permill, u128, u128),
+        /// 当争议被撤回时触发此事件 [task_id]
+        DisputeDismissed(u128),
+        /// 当仲裁小组给出裁决时触发此事件 [task_id, receiver_share, appeal_deadline]
+        DisputeRuled(u128, Permill, BlockNumber),
+        /// 当向理事会上诉时触发此事件 [appellant, task_id, appeal_fee]
+        RulingAppealed(AccountId, u128, u128),
+        /// 当理事会给出最终判决时触发此事件 [task_id, receiver_share]
+        FinalJudgment(u128, Permill),
+        /// 当任务到期被自动变更状态时触发此事件 [task_id, task_status]
+        TaskExpired(u128, TaskStatus),
+        /// 当截止事件重试MaxDeadlineRetries次仍处理失败、从队列中移除时触发此事件 [task_id, kind]
+        DeadlineDropped(u128, DeadlineKind),
+        /// 当中标者未在交付截止前交付、投标质押被罚没时触发此事件 [receiver, task_id, slashed_stake]
+        ReceiverDefaulted(AccountId, u128, u128),
+        /// 当验收期结束、任务被自动验收并结算时触发此事件 [owner, receiver, task_id]
+        TaskAutoAccepted(AccountId, AccountId, u128),
+        /// 当中标者交付里程碑时触发此事件 [receiver, task_id, milestone_index]
+        MilestoneDelivered(AccountId, u128, u32),
+        /// 当里程碑结清、奖励支付给中标者时触发此事件 [task_id, milestone_index, amount]
+        MilestonePaid(u128, u32, u128),
+        /// 当针对里程碑发起仲裁时触发此事件 [plaintiff, task_id, milestone_index]
+        MilestoneDisputed(AccountId, u128, u32),
+        /// 当验收期结束、里程碑被自动验收时触发此事件 [task_id, milestone_index]
+        MilestoneAutoAccepted(u128, u32),
+        /// 当任务创建者为中标者评分时触发此事件 [owner, receiver, task_id, rating]
+        TaskRated(AccountId, AccountId, u128, u8),
+        /// 当任务创建者设置投标策略时触发此事件 [owner, task_id]
+        BidPolicySet(AccountId, u128),
+        /// 当任务创建者设置或取消密封投标时触发此事件 [owner, task_id, reveal_close]
+        SealedBiddingSet(AccountId, u128, Option<BlockNumber>),
+        /// 当提交密封投标时触发此事件 [bidder, task_id, stake_token]
+        BidCommitted(AccountId, u128, u128),
+        /// 当揭示密封投标时触发此事件 [bidder, task_id, price]
+        BidRevealed(AccountId, u128, u128),
+        /// 当未揭示的密封投标被罚没时触发此事件 [bidder, task_id, refunded_stake, penalty]
+        BidForfeited(AccountId, u128, u128, u128),
+        /// 当任务创建者设置或取消自动选标时触发此事件 [owner, task_id]
+        AutoAwardSet(AccountId, u128),
+        /// 当投标截止后自动选出报价最低的合格投标人时触发此事件 [receiver, task_id, price]
+        TaskAutoAwarded(AccountId, u128, u128),
+        /// 当中标方案成为任务约定条款时触发此事件 [receiver, task_id, price, delivery, refunded_to_owner]
+        TaskTermsBound(AccountId, u128, u128, BlockNumber, u128),
+        /// 当投标人撤回投标时触发此事件 [bidder, task_id, refunded_stake, withdrawal_fee]
+        BidWithdrawn(AccountId, u128, u128, u128),
+        /// 当投标人修改投标时触发此事件 [bidder, task_id, stake_token]
+        BidAmended(AccountId, u128, u128),
+        /// 当新增任务分类时触发此事件 [category_id, name]
+        CategoryAdded(u32, Vec<u8>),
+        /// 当移除任务分类时触发此事件 [category_id]
+        CategoryRemoved(u32),
+        /// 当中标者提交交付物时触发此事件 [receiver, task_id, deliverable_hash]
+        DeliverableSubmitted(AccountId, u128, Hash),
+        /// 当为中标者铸造任务完成凭证时触发此事件 [receiver, task_id]
+        CredentialIssued(AccountId, u128),
+        /// 当中标者从父任务的锁定奖励中划转创建子任务时触发此事件 [receiver, parent_task_id, task_id, stake_token]
+        SubTaskCreated(AccountId, u128, u128, u128),
+        /// 当已结束或已撤销的任务被清除、存储押金退还时触发此事件 [owner, task_id, deposit]
+        TaskRemoved(AccountId, u128, u128),
+        /// 当仲裁员回避、由新抽取的仲裁员替换时触发此事件 [arbitrator, task_id, replacement]
+        ArbitratorReplaced(AccountId, u128, AccountId),
+        /// 当投票期结束、未投票的仲裁员被移出仲裁小组时触发此事件 [task_id, absent_arbitrators]
+        AbsentArbitratorsRemoved(u128, Vec<AccountId>),
+        /// 当投票期结束仍无人投票、争议提交理事会判决时触发此事件 [task_id]
+        DisputeEscalated(u128),
 	}
 );
 
@@ -101,12 +420,68 @@ decl_error! {
 	pub enum Error for Module<T: Config> {
 		/// 该任务已经存在
 		TaskAlreadyExisted,
-        /// 该任务详情内容长度超出最大限制
+        /// 任务ID已用完
+        TaskIdOverflow,
+        /// 同一创建者的客户端引用不能重复
+        DuplicateClientRef,
+        /// 进行中的创建任务数量超出最大限制
+        TooManyOpenTasks,
+        /// 持有的投标数量超出最大限制
+        TooManyActiveBids,
+        /// 任务的投标人数量超出最大限制
+        TooManyBidders,
+        /// 评分必须在1~5之间
+        InvalidRating,
+        /// 该任务已经评过分了
+        AlreadyRated,
+        /// 信誉记录不满足该任务的投标要求
+        InsufficientReputation,
+        /// 该任务只允许白名单中的账户投标
+        NotAllowlisted,
+        /// 该任务只允许持有指定NFT的账户投标
+        NotClassHolder,
+        /// 投标白名单的账户数量超出最大限制
+        TooManyAllowlisted,
+        /// 密封投标的任务只能提交报价哈希并在揭示阶段揭示
+        SealedTask,
+        /// 该任务不是密封投标
+        NotSealedTask,
+        /// 不在密封投标的揭示阶段
+        NotRevealPhase,
+        /// 密封投标的揭示阶段尚未结束
+        RevealNotClosed,
+        /// 揭示的报价与随机盐与提交的哈希不一致
+        InvalidReveal,
+        /// 已收到投标的任务不能修改投标模式
+        BiddingModeLocked,
+        /// 自动选标的任务不能手动选标
+        AutoAwardTask,
+        /// 该任务分类不存在
+        NoSuchCategory,
+        /// 分类名称不能为空且长度不能超出最大限制
+        InvalidCategoryName,
+        /// 任务分类ID已用完
+        CategoryIdOverflow,
+        /// 标签数量超出最大限制
+        TooManyTags,
+        /// 标签不能为空、长度不能超出最大限制且不能重复
+        InvalidTag,
+        /// 任务标题不能为空
         InvalidTaskDetail,
+        /// 任务标题长度超出最大限制
+        TitleTooLong,
+        /// 任务地址长度超出最大限制
+        UriTooLong,
+        /// 任务地址不是有效的IPFS CID
+        InvalidTaskUri,
 		/// 该任务不存在
 		NoSuchTask,
 		/// 该任务不是本人创建的
 		NotTaskOwner,
+        /// 报价必须大于0且不超过任务奖励
+        InvalidBidPrice,
+        /// 预计交付区块必须晚于当前区块且不晚于任务的交付截止区块
+        InvalidBidDelivery,
         /// 不能投标自己的任务
         NotBidSelf,
         /// 该任务投标通道已关闭
@@ -117,6 +492,10 @@ decl_error! {
         DelegateClosed,
         /// 该用户没有投标这个任务
         NoSuchBidder,
+        /// 中标团队不能为空、成员不能重复，且每个比例大于0、总和为100%
+        InvalidTeam,
+        /// 中标人数超出最大限制
+        TooManyReceivers,
         /// 该操作只有任务创建者或中标者才有权限
         NotTaskOwnerOrReceiver,
         /// 该操作只有任务中标者才有权限
@@ -125,7 +504,69 @@ decl_error! {
         InvalidTaskStatus,
         /// 质押token数量太少
         InvalidStakeToken,
-        
+        /// 可用余额不足以支付质押
+        InsufficientBalance,
+        /// 该任务还没有中标人
+        NoTaskReceiver,
+        /// 任务创建者锁定的质押不足以完成结算
+        InsufficientEscrow,
+        /// 只有投标中或已撤销状态的任务才能被撤销
+        RevokeClosed,
+        /// 不允许从当前任务状态变更到目标状态
+        InvalidStatusTransition,
+        /// 进入进行中状态只能通过选标完成
+        DelegateRequired,
+        /// 进入仲裁或结束仲裁只能通过仲裁流程完成
+        DisputeRequired,
+        /// 已经是仲裁员
+        AlreadyArbitrator,
+        /// 不是仲裁员
+        NotArbitrator,
+        /// 仲裁员池已满
+        TooManyArbitrators,
+        /// 仲裁员还有未结的案件
+        ArbitratorBusy,
+        /// 可抽取的仲裁员人数不足
+        NotEnoughArbitrators,
+        /// 该任务没有争议案件
+        NoSuchDispute,
+        /// 不是该案件仲裁小组的成员
+        NotPanelMember,
+        /// 同一仲裁员同一案件不能重复投票
+        AlreadyVoted,
+        /// 证据数量超出最大限制
+        TooManyEvidence,
+        /// 仲裁小组尚未给出裁决
+        NoRuling,
+        /// 仲裁小组已经给出裁决
+        AlreadyRuled,
+        /// 上诉期已结束
+        AppealPeriodOver,
+        /// 上诉期尚未结束
+        AppealPeriodNotOver,
+        /// 无效的任务截止时间
+        InvalidDeadline,
+        /// 已超过交付截止时间
+        DeliveryOverdue,
+        /// 里程碑数量超出最大限制
+        TooManyMilestones,
+        /// 里程碑比例必须大于0且总和为100%
+        InvalidMilestones,
+        /// 该任务没有这个里程碑
+        NoSuchMilestone,
+        /// 只能操作当前（第一个未结清的）里程碑
+        NotCurrentMilestone,
+        /// 里程碑状态不允许该操作
+        InvalidMilestoneStatus,
+        /// 设置了里程碑的任务需按里程碑交付、验收与发起仲裁
+        MilestoneTask,
+        /// 还有未结束、未撤销的子任务
+        OpenSubTasks,
+        /// 仲裁小组的投票期尚未结束
+        RulingPeriodNotOver,
+        /// 只有已结束或已撤销的任务才能被清除
+        TaskNotClosed,
+
 	}
 }
 
@@ -140,72 +581,130 @@ decl_module! {
 		// Events must be initialized if they are used by the pallet.
 		fn deposit_event() = default;
 
-		/// 创建任务
+		/// 注册成为仲裁员所需的最低质押
+		const MinArbitratorStake: Balance = T::MinArbitratorStake::get();
+
+		/// 发起仲裁所需缴纳的保证金
+		const DisputeBond: Balance = T::DisputeBond::get();
+
+		/// 每个争议案件的仲裁小组人数
+		const ArbitratorPanelSize: u32 = T::ArbitratorPanelSize::get();
+
+		/// 仲裁员池的最大人数
+		const MaxArbitrators: u32 = T::MaxArbitrators::get();
+
+		/// 每个争议案件最多可提交的证据数量
+		const MaxEvidence: u32 = T::MaxEvidence::get();
+
+		/// 每个区块最多处理的到期任务数量
+		const MaxDeadlinesPerBlock: u32 = T::MaxDeadlinesPerBlock::get();
+
+		/// 处理失败的截止事件最多重试的次数
+		const MaxDeadlineRetries: u32 = T::MaxDeadlineRetries::get();
+
+		/// 默认验收期（区块数）
+		const ReviewPeriod: T::BlockNumber = T::ReviewPeriod::get();
+
+		/// 每个任务最多可设置的里程碑数量
+		const MaxMilestones: u32 = T::MaxMilestones::get();
+
+		/// 任务标题的最大字节数
+		const MaxTitleLength: u32 = T::MaxTitleLength::get();
+
+		/// 任务链下存储地址的最大字节数
+		const MaxUriLength: u32 = T::MaxUriLength::get();
+
+		/// 任务详情每字节需缴纳的存储押金
+		const DepositPerByte: Balance = T::DepositPerByte::get();
+
+		/// 每个账户最多同时进行中的创建任务数量
+		const MaxOpenTasksPerAccount: u32 = T::MaxOpenTasksPerAccount::get();
+
+		/// 每个账户最多同时持有的投标数量
+		const MaxActiveBidsPerAccount: u32 = T::MaxActiveBidsPerAccount::get();
+
+		/// 每个任务最多的投标人数量
+		const MaxBiddersPerTask: u32 = T::MaxBiddersPerTask::get();
+
+		/// 撤回投标时从投标质押中扣除的手续费比例
+		const BidWithdrawalFee: Permill = T::BidWithdrawalFee::get();
+
+		/// 每个任务最多可附带的标签数量
+		const MaxTagsPerTask: u32 = T::MaxTagsPerTask::get();
+
+		/// 标签与分类名称的最大字节数
+		const MaxTagLength: u32 = T::MaxTagLength::get();
+
+		/// 团队任务最多的中标人数
+		const MaxTeamSize: u32 = T::MaxTeamSize::get();
+
+		/// 投标白名单的最大账户数量
+		const MaxBidAllowlist: u32 = T::MaxBidAllowlist::get();
+
+		/// 密封投标未揭示时被罚没的投标质押比例
+		const UnrevealedBidPenalty: Permill = T::UnrevealedBidPenalty::get();
+
+		/// 上诉所需缴纳的费用
+		const AppealFee: Balance = T::AppealFee::get();
+
+		/// 仲裁裁决后的上诉期（区块数）
+		const AppealPeriod: T::BlockNumber = T::AppealPeriod::get();
+
+		/// 仲裁小组的投票期（区块数）
+		const RulingPeriod: T::BlockNumber = T::RulingPeriod::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::process_deadlines(now)
+		}
+
+		/// 创建任务 任务ID由链上分配，并在TaskCreated事件中返回
+        /// `client_ref`为调用方自定义的引用，用于关联链下记录
         #[weight = 10_000]
-        fn create_task(origin, task_id: u128, stake_token: u128, task_detail: Vec<u8>) -> dispatch::DispatchResult {
+        #[transactional]
+        fn create_task(origin, currency_id: CurrencyIdOf<T>, stake_token: u128, title: Vec<u8>, content_hash: T::Hash, uri: Vec<u8>,
+            deadline: TaskDeadline<T::BlockNumber>, milestones: Vec<Permill>, client_ref: Option<u128>, category: Option<u32>,
+            tags: Vec<Vec<u8>>) -> dispatch::DispatchResult {
             // 检查当前操作者是否已签名
             // 如果未签名，则返回错误
             let sender = ensure_signed(origin)?;
 
-            // 检查任务ID是否已存在
-            ensure!(!TaskDetail::<T>::contains_key(&task_id), Error::<T>::TaskAlreadyExisted);
-
-            // 检查质押token数量 必须大于0
-            ensure!(stake_token > 0, Error::<T>::InvalidStakeToken);
-
-            // 检查任务详情内容是否超过最大字节数
-            //ensure!(task_detail.len() <= config::TASK_DETAIL_MAX_BYTES, Error::<T>::InvalidTaskDetail);
-
-            // 从FRAME系统模块中获取当前区块高度
-            let current_block = <frame_system::Module<T>>::block_number();
-
-            // 初始任务状态
-            let task_status = TaskStatus::Bidding as u8;
-
-            // TODO 质押
-
-            // 保存任务详情
-            TaskDetail::<T>::insert(&task_id, (sender.clone(), task_detail.clone(), current_block));
-            RelTaskStatus::insert(&task_id, task_status.clone());
-            RelCreateTaskStake::insert(&task_id, stake_token.clone());
-
-            // 更新我创建的任务列表
-            let mut my_tasks = MyCreateTasks::<T>::get(&sender);
-            my_tasks.insert(task_id.clone());
-            MyCreateTasks::<T>::insert(&sender, my_tasks);
-
-            // 任务总数+1
-            let task_count = TaskCount::get();
-            match task_count.checked_add(1) { 
-                Some(v)=> { TaskCount::put(v); }, 
-                None => (), 
-            }   
-            
-            // 更新用户列表
-            let mut user_list = UserList::<T>::get();
-            if !user_list.contains(&sender) {
-                user_list.insert(sender.clone());
-                UserList::<T>::put(user_list);
-            }
-
-            // 更新任务列表
-            let mut task_list = TaskList::get();
-            if !task_list.contains(&task_id) {
-                task_list.insert(task_id.clone());
-                TaskList::put(task_list);
-            }
-
-            // 触发创建任务事件 
-			Self::deposit_event(RawEvent::TaskCreated(sender, task_id, task_status, stake_token, task_detail));
+            Self::do_create_task(sender, None, currency_id, stake_token, title, content_hash, uri, deadline, milestones,
+                client_ref, category, tags)?;
 
 			// Return a successful DispatchResult
 			Ok(())
 			
         }
 
+        /// 创建子任务 中标者在任务进行中将部分工作外包，子任务的奖励从父任务的锁定奖励中划转，币种与父任务相同
+        /// 子任务ID在TaskCreated与SubTaskCreated事件中返回
+        #[weight = 10_000]
+        #[transactional]
+        fn create_subtask(origin, parent_id: u128, stake_token: u128, title: Vec<u8>, content_hash: T::Hash, uri: Vec<u8>,
+            deadline: TaskDeadline<T::BlockNumber>, milestones: Vec<Permill>, client_ref: Option<u128>, category: Option<u32>,
+            tags: Vec<Vec<u8>>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查父任务状态与操作权限
+            Self::ensure_can_subcontract(parent_id, &sender, &deadline)?;
+
+            let currency_id = Self::task_currency_id(parent_id);
+            let task_id = Self::do_create_task(sender.clone(), Some(parent_id), currency_id, stake_token, title, content_hash,
+                uri, deadline, milestones, client_ref, category, tags)?;
+
+            Self::deposit_event(RawEvent::SubTaskCreated(sender, parent_id, task_id, stake_token));
+
+            Ok(())
+        }
+
         /// 更新任务状态        
         #[weight = 10_000]
-        fn update_task_status(origin, task_id: u128, task_status: u8) -> dispatch::DispatchResult {
+        #[transactional]
+        fn update_task_status(origin, task_id: u128, task_status: TaskStatus) -> dispatch::DispatchResult {
             // 检查调用者是否已签名
             // 如果未签名，则函数将返回错误
             let sender = ensure_signed(origin)?;
@@ -213,111 +712,239 @@ decl_module! {
             // 检查任务是否存在
             ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
 
-            let current_task_status = RelTaskStatus::get(&task_id);
+            let current_task_status = RelTaskStatus::get(&task_id).ok_or(Error::<T>::InvalidTaskStatus)?;
 
-            // 检查任务状态值的有效性
-            ensure!((task_status > current_task_status) //禁止任务状态回退
-                 && (task_status == (TaskStatus::Bidding as u8)
-                 || task_status  == (TaskStatus::Doing as u8)
-                 || task_status  == (TaskStatus::UnDone as u8)
-                 || task_status  == (TaskStatus::Delivered as u8)
-                 || task_status  == (TaskStatus::Accepted as u8)
-                 || task_status  == (TaskStatus::Arbitrating as u8) 
-                 || task_status  == (TaskStatus::Judging as u8) 
-                 || task_status  == (TaskStatus::Finished as u8)), Error::<T>::InvalidTaskStatus);
+            // 按状态转换表检查状态变更是否合法，并得到有权限的操作者
+            let actor = current_task_status.transition_to(task_status).ok_or(Error::<T>::InvalidStatusTransition)?;
 
             // 获取任务创建者
-            let (owner, _, _) = TaskDetail::<T>::get(&task_id);
+            let owner = TaskDetail::<T>::get(&task_id).owner;
+
+            // 检查操作权限 团队任务的任何成员都视为中标者
+            let is_receiver = Self::is_team_member(task_id, &sender);
+            match actor {
+                StatusActor::Owner => ensure!(sender == owner, Error::<T>::NotTaskOwner),
+                StatusActor::Receiver => ensure!(is_receiver, Error::<T>::NotTaskReceiver),
+                StatusActor::OwnerOrReceiver => ensure!((sender == owner || is_receiver), Error::<T>::NotTaskOwnerOrReceiver),
+                StatusActor::Delegation => return Err(Error::<T>::DelegateRequired.into()),
+                StatusActor::Arbitration => return Err(Error::<T>::DisputeRequired.into()),
+                StatusActor::Expiry => return Err(Error::<T>::InvalidStatusTransition.into()),
+            }
 
-            // 获取任务中标者
-            let receiver = RelTaskReceiver::<T>::get(&task_id);
+            // 仲裁小组已作出裁决后不能再撤回争议，只能等待上诉期结束或上诉
+            if current_task_status == TaskStatus::Arbitrating {
+                ensure!(!DisputeRulings::<T>::contains_key(&task_id), Error::<T>::AlreadyRuled);
+            }
 
-            // 检查操作权限
-            // 任务状态的更新默认只有任务创建者才有权限
-            // 但其中Completed只有任务中标者有权限操作，Arbitrating、Judging两个状态双方都有权限操作
-            if task_status == (TaskStatus::Delivered as u8) {
-                ensure!(sender == receiver, Error::<T>::NotTaskReceiver);
-            } else if task_status == (TaskStatus::Arbitrating as u8) || task_status == (TaskStatus::Judging as u8) {
-                ensure!((sender == owner || sender == receiver), Error::<T>::NotTaskOwnerOrReceiver);
-            } else {
-                ensure!(sender == owner, Error::<T>::NotTaskOwner);
-            }            
+            // 设置了里程碑的任务需按里程碑交付与验收
+            if task_status == TaskStatus::Delivered || task_status == TaskStatus::Accepted {
+                ensure!(!TaskMilestones::contains_key(&task_id), Error::<T>::MilestoneTask);
+                // 所有子任务结束或撤销后才能交付与验收
+                ensure!(!Self::has_open_subtasks(task_id), Error::<T>::OpenSubTasks);
+            }
 
-            // 更新任务状态            
-            RelTaskStatus::insert(&task_id, task_status.clone());
+            // 检查是否已超过交付截止时间
+            if task_status == TaskStatus::Delivered {
+                if let Some(delivery) = TaskDeadlines::<T>::get(&task_id).delivery {
+                    ensure!(<frame_system::Module<T>>::block_number() < delivery, Error::<T>::DeliveryOverdue);
+                }
+            }
 
-            // TODO 任务正常完成后 
-            let finished = TaskStatus::Finished as u8;
-            if finished == task_status {
-                //TODO 自动解除质押、转账
+            // 更新任务状态            
+            Self::set_task_status(task_id, task_status);
+
+            // 执行状态变更的附带操作
+            let mut updated_status = task_status;
+            match task_status {
+                // 任务在投标中被撤销 退还创建者与所有投标人的质押
+                TaskStatus::UnDone => Self::release_stakes(task_id, &owner)?,
+                // 任务已交付 开始计算验收期
+                TaskStatus::Delivered => Self::schedule_review(task_id),
+                // 创建者验收任务 取消验收期；在仲裁中验收视为撤回争议
+                // 验收即结算任务奖励并退还中标人的投标质押，任务直接变更为已完成
+                TaskStatus::Accepted => {
+                    Self::cancel_review(task_id);
+                    Self::dismiss_dispute(task_id);
+                    ensure!(RelTaskReceiver::<T>::contains_key(&task_id), Error::<T>::NoTaskReceiver);
+                    Self::settle_task(task_id, &owner)?;
+                    Self::set_task_status(task_id, TaskStatus::Finished);
+                    updated_status = TaskStatus::Finished;
+                },
+                // 任务正常完成后 自动解除质押、转账
+                TaskStatus::Finished => {
+                    ensure!(RelTaskReceiver::<T>::contains_key(&task_id), Error::<T>::NoTaskReceiver);
+                    Self::settle_task(task_id, &owner)?;
+                },
+                _ => (),
             }
 
             // 触发修改任务事件
-            Self::deposit_event(RawEvent::TaskStatusUpdated(sender, task_id, task_status));
+            Self::deposit_event(RawEvent::TaskStatusUpdated(sender, task_id, updated_status));
 
 			// Return a successful DispatchResult
 			Ok(())
 
         }
 
-        /// 投标任务
+        /// 投标任务 密封投标的任务需通过commit_bid提交
         #[weight = 10_000]
-        fn bid_task(origin, task_id: u128, stake_token: u128) -> dispatch::DispatchResult {
+        fn bid_task(origin, task_id: u128, stake_token: u128, price: Balance, delivery: T::BlockNumber, proposal_hash: T::Hash) -> dispatch::DispatchResult {
             // 检查当前操作者是否已签名
             // 如果未签名，则返回错误
             // https://substrate.dev/docs/en/knowledgebase/runtime/origin
             let sender = ensure_signed(origin)?;
 
-            // 获取任务创建者
-            let (owner, _, _) = TaskDetail::<T>::get(&task_id);
+            // 检查投标人、投标质押与投标策略
+            Self::ensure_can_bid(task_id, &sender, stake_token)?;
+            ensure!(!Self::is_sealed(task_id), Error::<T>::SealedTask);
+
+            // 检查投标方案
+            let proposal = BidProposal { price, delivery, proposal_hash };
+            Self::ensure_valid_proposal(task_id, &proposal)?;
+
+            // 质押（锁定）投标token，并保存投标方案
+            Self::insert_bid(task_id, &sender, stake_token)?;
+            BidProposals::<T>::insert(&task_id, &sender, proposal);
+
+            // 触发投标任务事件
+            Self::deposit_event(RawEvent::BidCompleted(sender, task_id, stake_token));
+
+            // Return a successful DispatchResult
+			Ok(())
+
+        }
+
+        /// 提交密封投标 在投标截止前缴纳投标质押，只提交报价与随机盐的哈希 hash(task_id, bidder, price, salt)
+        #[weight = 10_000]
+        fn commit_bid(origin, task_id: u128, stake_token: u128, commitment: T::Hash) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查投标人、投标质押与投标策略
+            Self::ensure_can_bid(task_id, &sender, stake_token)?;
+            ensure!(Self::is_sealed(task_id), Error::<T>::NotSealedTask);
+
+            // 质押（锁定）投标token，并保存报价哈希
+            Self::insert_bid(task_id, &sender, stake_token)?;
+            BidCommitments::<T>::insert(&task_id, &sender, commitment);
+
+            Self::deposit_event(RawEvent::BidCommitted(sender, task_id, stake_token));
+
+            Ok(())
+        }
+
+        /// 揭示密封投标 在揭示阶段公开报价、随机盐与投标方案，哈希一致才视为有效投标
+        #[weight = 10_000]
+        fn reveal_bid(origin, task_id: u128, price: Balance, salt: T::Hash, delivery: T::BlockNumber, proposal_hash: T::Hash) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务是否在揭示阶段，报价与随机盐是否与提交的哈希一致
+            Self::ensure_reveal_open(task_id)?;
+            Self::ensure_matches_commitment(task_id, &sender, price, &salt)?;
+
+            // 检查投标方案
+            let proposal = BidProposal { price, delivery, proposal_hash };
+            Self::ensure_valid_proposal(task_id, &proposal)?;
+
+            BidCommitments::<T>::remove(&task_id, &sender);
+            BidProposals::<T>::insert(&task_id, &sender, proposal);
+
+            Self::deposit_event(RawEvent::BidRevealed(sender, task_id, price));
+
+            Ok(())
+        }
+
+        /// 设置密封投标 只有投标中且尚未收到投标的任务才能设置，`reveal_close`为揭示截止区块，为空时取消密封投标
+        #[weight = 10_000]
+        fn set_sealed_bidding(origin, task_id: u128, reveal_close: Option<T::BlockNumber>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务状态与操作权限
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+            ensure!(sender == TaskDetail::<T>::get(&task_id).owner, Error::<T>::NotTaskOwner);
+            Self::ensure_bidding_open(task_id)?;
+            ensure!(RelTaskBidder::<T>::get(&task_id).is_empty(), Error::<T>::BiddingModeLocked);
+
+            if let Some(reveal_close) = reveal_close {
+                Self::ensure_valid_reveal_close(task_id, reveal_close)?;
+            }
+            Self::schedule_reveal(task_id, reveal_close);
+
+            Self::deposit_event(RawEvent::SealedBiddingSet(sender, task_id, reveal_close));
+
+            Ok(())
+        }
+
+        /// 撤回投标 退还投标质押（扣除撤回手续费），只能在投标中状态操作
+        #[weight = 10_000]
+        #[transactional]
+        fn withdraw_bid(origin, task_id: u128) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
 
-            // 检查当前操作者是否为任务的创建者 不能自己投标自己
-            ensure!(sender != owner, Error::<T>::NotBidSelf);   
-            
             // 检查任务是否存在
             ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
 
-            // 检查质押token是否满足要求：任务创建者质押数量的1/10
-            let create_task_stake = RelCreateTaskStake::get(&task_id);
-            ensure!(stake_token < (((create_task_stake as f32) * config::BID_STAKE_RATIO) as u128), Error::<T>::InvalidStakeToken);
+            // 检查任务是否在投标中且未超过投标截止时间 密封投标的任务只能在提交阶段撤回
+            Self::ensure_bidding_open(task_id)?;
 
-            // 检查任务状态是否为投标中
-            let task_status = RelTaskStatus::get(&task_id);
-            ensure!((TaskStatus::Bidding as u8) == task_status, Error::<T>::BidClosed);
+            // 检查是否已投标
+            ensure!(RelBidTaskStake::<T>::contains_key(&task_id, &sender), Error::<T>::NoSuchBidder);
 
-            // 检查是否已经投标过了
-            ensure!(!RelBidTaskStake::<T>::contains_key(&task_id, &sender), Error::<T>::NoDuplicateBid);
+            // 扣除撤回手续费后退还投标质押，并清除投标记录
+            let (refunded, fee) = Self::withdraw_bid_stake(task_id, &sender, T::BidWithdrawalFee::get())?;
+            RelTaskBidder::<T>::mutate(&task_id, |bidders| bidders.remove(&sender));
 
-            // TODO 质押token
+            Self::deposit_event(RawEvent::BidWithdrawn(sender, task_id, refunded, fee));
 
-            // 保存投标任务与质押的Token数量关系
-            RelBidTaskStake::<T>::insert(&task_id, &sender, stake_token.clone());
+            Ok(())
+        }
 
-            // 更新任务与投标人关系
-            let mut task_bidder = RelTaskBidder::<T>::get(&task_id);
-            if !task_bidder.contains(&sender) {
-                task_bidder.insert(sender.clone());
-                RelTaskBidder::<T>::insert(&task_id, task_bidder);
-            }
+        /// 修改投标 调整投标质押与投标方案，只能在投标中状态操作
+        #[weight = 10_000]
+        #[transactional]
+        fn amend_bid(origin, task_id: u128, stake_token: u128, price: Balance, delivery: T::BlockNumber, proposal_hash: T::Hash) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
 
-            // 更新我投标的任务列表
-            let mut my_bid_tasks = MyBidTasks::<T>::get(&sender);
-            if !my_bid_tasks.contains(&task_id) {
-                my_bid_tasks.insert(task_id.clone());
-                MyBidTasks::<T>::insert(&sender, my_bid_tasks);
-            } 
+            // 检查任务是否存在
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
 
-            // 触发投标任务事件
-            Self::deposit_event(RawEvent::BidCompleted(sender, task_id, stake_token));
+            // 检查任务是否在投标中且未超过投标截止时间 密封投标的任务不能修改投标
+            Self::ensure_bidding_open(task_id)?;
+            ensure!(!Self::is_sealed(task_id), Error::<T>::SealedTask);
+
+            // 检查是否已投标
+            ensure!(RelBidTaskStake::<T>::contains_key(&task_id, &sender), Error::<T>::NoSuchBidder);
+
+            // 检查质押token与投标方案
+            Self::ensure_valid_bid_stake(task_id, stake_token)?;
+            let proposal = BidProposal { price, delivery, proposal_hash };
+            Self::ensure_valid_proposal(task_id, &proposal)?;
+
+            // 按差额追加或退还投标质押
+            let currency_id = Self::task_currency_id(task_id);
+            let old_stake = RelBidTaskStake::<T>::get(&task_id, &sender);
+            if stake_token > old_stake {
+                let extra = stake_token - old_stake;
+                ensure!(T::MultiCurrency::can_reserve(currency_id, &sender, extra), Error::<T>::InsufficientBalance);
+                T::MultiCurrency::reserve(currency_id, &sender, extra)?;
+            } else {
+                T::MultiCurrency::unreserve(currency_id, &sender, old_stake - stake_token);
+            }
 
-            // Return a successful DispatchResult
-			Ok(())
+            RelBidTaskStake::<T>::insert(&task_id, &sender, stake_token);
+            BidProposals::<T>::insert(&task_id, &sender, proposal);
+            Self::touch_bid_sequence(task_id, &sender);
+
+            Self::deposit_event(RawEvent::BidAmended(sender, task_id, stake_token));
 
+            Ok(())
         }
 
         /// 任务选标（中标）
+        /// `receivers`为中标团队及每个成员分配任务奖励的比例，单人中标时为 [(bidder, 100%)]；
+        /// 第一个成员为团队负责人，按其投标方案约定任务条款
         #[weight = 10_000]
-        fn delegate_task(origin, bidder: T::AccountId, task_id: u128) -> dispatch::DispatchResult {
+        #[transactional]
+        fn delegate_task(origin, receivers: Vec<(T::AccountId, Permill)>, task_id: u128) -> dispatch::DispatchResult {
             // 检查当前操作者是否已签名
             // 如果未签名，则返回错误
             // https://substrate.dev/docs/en/knowledgebase/runtime/origin
@@ -327,37 +954,25 @@ decl_module! {
             ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
 
             // 获取任务创建者
-            let (owner, _, _) = TaskDetail::<T>::get(&task_id);
+            let owner = TaskDetail::<T>::get(&task_id).owner;
 
             // 检查当前操作者是否为任务创建者
             ensure!(sender == owner, Error::<T>::NotTaskOwner);
 
             // 检查任务状态是否为投标中 RelTaskStatus
             let task_status = RelTaskStatus::get(&task_id);
-            ensure!((TaskStatus::Bidding as u8) == task_status, Error::<T>::DelegateClosed);
-
-            // 检查bidder是否已投标 忽略：检查bidder在本任务上是否已质押
-            let task_bidder = RelTaskBidder::<T>::get(&task_id);
-            ensure!(task_bidder.contains(&bidder), Error::<T>::NoSuchBidder);
+            ensure!(Some(TaskStatus::Bidding) == task_status, Error::<T>::DelegateClosed);
 
-            // 保存任务的中标人
-            RelTaskReceiver::<T>::insert(&task_id, bidder.clone());
-
-            // 更新任务状态 进入Doing状态
-            let task_status = TaskStatus::Doing as u8;
-            RelTaskStatus::insert(&task_id, task_status);
-
-            // 更新我中标的任务列表
-            let mut my_receive_tasks = MyReceiveTasks::<T>::get(&bidder);
-            if !my_receive_tasks.contains(&task_id) {
-                my_receive_tasks.insert(task_id.clone());
-                MyReceiveTasks::<T>::insert(&bidder, my_receive_tasks);
+            // 密封投标的任务在揭示截止后才能选标，并罚没未揭示的投标
+            if Self::is_sealed(task_id) {
+                Self::ensure_reveal_closed(task_id)?;
+                Self::forfeit_unrevealed(task_id)?;
             }
-            
-            // 退还未中标人的质押、清空RelBidTaskStake为0（除中标人外）
 
-            // 触发选标任务事件
-            Self::deposit_event(RawEvent::TaskDelegated(sender, bidder, task_id));
+            // 自动选标的任务由投标截止时的链上竞价决定中标人
+            ensure!(!AutoAward::contains_key(&task_id), Error::<T>::AutoAwardTask);
+
+            Self::do_delegate(task_id, &owner, receivers)?;
 
             // Return a successful DispatchResult
 			Ok(())
@@ -366,6 +981,7 @@ decl_module! {
 
         /// 撤销任务
         #[weight = 10_000]
+        #[transactional]
         fn revoke_task(origin, task_id: u128) -> dispatch::DispatchResult {
             // 检查当前操作者是否已签名
             // 如果未签名，则返回错误
@@ -376,24 +992,21 @@ decl_module! {
             ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
 
             // 获取任务创建者
-            let (owner, _, _) = TaskDetail::<T>::get(&task_id);
+            let owner = TaskDetail::<T>::get(&task_id).owner;
 
             // 检查当前操作者是否为任务创建者
             ensure!(sender == owner, Error::<T>::NotTaskOwner);
 
-            // 从链上存储中撤销任务
-            TaskDetail::<T>::remove(&task_id);
+            // 检查任务状态 只有投标中或已撤销的任务才能撤销
+            let task_status = RelTaskStatus::get(&task_id);
+            ensure!(Some(TaskStatus::Bidding) == task_status
+                || Some(TaskStatus::UnDone) == task_status, Error::<T>::RevokeClosed);
 
-            // 更新我的任务集
-            let mut my_create_tasks = MyCreateTasks::<T>::get(&sender);
-            if my_create_tasks.contains(&task_id) {
-                my_create_tasks.remove(&task_id); //移除指定task_id
-            }
+            // 所有子任务结束或撤销后才能撤销
+            ensure!(!Self::has_open_subtasks(task_id), Error::<T>::OpenSubTasks);
 
-            // TODO 退还本人与所有投标人的质押
-            
-            // 任务总数-1
-            TaskCount::mutate(|v| *v -= 1);
+            // 退还本人与所有投标人的质押，并从链上存储中清除任务及其所有关系信息
+            Self::remove_task(task_id, &owner)?;
 
             // 触发撤销任务事件
             Self::deposit_event(RawEvent::TaskRevoked(sender, task_id));
@@ -403,6 +1016,746 @@ decl_module! {
 
         }
 
+        /// 清除已结束或已撤销的任务 退还存储押金，并从链上存储中清除任务及其所有关系信息
+        #[weight = 10_000]
+        #[transactional]
+        fn remove_closed_task(origin, task_id: u128) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务是否存在 以及当前操作者是否为任务创建者
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+            let detail = TaskDetail::<T>::get(&task_id);
+            ensure!(sender == detail.owner, Error::<T>::NotTaskOwner);
+
+            // 检查任务状态 只有已结束或已撤销的任务才能清除
+            let task_status = RelTaskStatus::get(&task_id).ok_or(Error::<T>::InvalidTaskStatus)?;
+            ensure!(task_status == TaskStatus::Finished || task_status == TaskStatus::UnDone, Error::<T>::TaskNotClosed);
+
+            // 所有子任务结束或撤销后才能清除
+            ensure!(!Self::has_open_subtasks(task_id), Error::<T>::OpenSubTasks);
+
+            Self::remove_task(task_id, &sender)?;
+
+            Self::deposit_event(RawEvent::TaskRemoved(sender, task_id, detail.deposit));
+
+            Ok(())
+        }
+
+        /// 提交交付物 中标者在任务进行中或交付后提交交付物的哈希，重复提交时覆盖
+        #[weight = 10_000]
+        fn submit_deliverable(origin, task_id: u128, deliverable_hash: T::Hash) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务状态与操作权限
+            let task_status = RelTaskStatus::get(&task_id).ok_or(Error::<T>::NoSuchTask)?;
+            ensure!(task_status == TaskStatus::Doing || task_status == TaskStatus::Delivered, Error::<T>::InvalidTaskStatus);
+            ensure!(Self::is_team_member(task_id, &sender), Error::<T>::NotTaskReceiver);
+
+            TaskDeliverables::<T>::insert(&task_id, deliverable_hash);
+
+            Self::deposit_event(RawEvent::DeliverableSubmitted(sender, task_id, deliverable_hash));
+
+            Ok(())
+        }
+
+        /// 交付里程碑 中标者按顺序交付当前里程碑，并开始计算验收期
+        #[weight = 10_000]
+        fn deliver_milestone(origin, task_id: u128, milestone_index: u32) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务状态与操作权限
+            ensure!(RelTaskStatus::get(&task_id) == Some(TaskStatus::Doing), Error::<T>::InvalidTaskStatus);
+            ensure!(Self::is_team_member(task_id, &sender), Error::<T>::NotTaskReceiver);
+
+            // 检查里程碑状态
+            let milestone = Self::ensure_current_milestone(task_id, milestone_index)?;
+            ensure!(milestone.status == MilestoneStatus::Pending, Error::<T>::InvalidMilestoneStatus);
+
+            // 检查是否已超过交付截止时间
+            if let Some(delivery) = TaskDeadlines::<T>::get(&task_id).delivery {
+                ensure!(<frame_system::Module<T>>::block_number() < delivery, Error::<T>::DeliveryOverdue);
+            }
+
+            Self::set_milestone_status(task_id, milestone_index, MilestoneStatus::Delivered);
+            Self::schedule_review(task_id);
+
+            Self::deposit_event(RawEvent::MilestoneDelivered(sender, task_id, milestone_index));
+
+            Ok(())
+        }
+
+        /// 验收里程碑 创建者验收已交付的里程碑，只支付该里程碑的奖励
+        /// 对仲裁中的里程碑验收视为撤回争议
+        #[weight = 10_000]
+        #[transactional]
+        fn accept_milestone(origin, task_id: u128, milestone_index: u32) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查操作权限
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+            let owner = TaskDetail::<T>::get(&task_id).owner;
+            ensure!(sender == owner, Error::<T>::NotTaskOwner);
+
+            // 检查里程碑状态
+            let milestone = Self::ensure_current_milestone(task_id, milestone_index)?;
+            match milestone.status {
+                MilestoneStatus::Delivered => (),
+                MilestoneStatus::Arbitrating => {
+                    // 已作出裁决或已上诉的里程碑争议只能按裁决或判决结算
+                    ensure!(RelTaskStatus::get(&task_id) == Some(TaskStatus::Arbitrating), Error::<T>::InvalidTaskStatus);
+                    ensure!(!DisputeRulings::<T>::contains_key(&task_id), Error::<T>::AlreadyRuled);
+                    DisputedMilestone::remove(&task_id);
+                    Self::dismiss_dispute(task_id);
+                },
+                _ => return Err(Error::<T>::InvalidMilestoneStatus.into()),
+            }
+
+            Self::pay_milestone(task_id, milestone_index, &owner)?;
+
+            Ok(())
+        }
+
+        /// 针对已交付的里程碑发起仲裁 仲裁只拆分该里程碑的奖励
+        #[weight = 10_000]
+        #[transactional]
+        fn dispute_milestone(origin, task_id: u128, milestone_index: u32, evidence: T::Hash) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务状态
+            ensure!(RelTaskStatus::get(&task_id) == Some(TaskStatus::Doing), Error::<T>::InvalidTaskStatus);
+
+            // 检查操作权限
+            let owner = TaskDetail::<T>::get(&task_id).owner;
+            ensure!((sender == owner || Self::is_team_member(task_id, &sender)), Error::<T>::NotTaskOwnerOrReceiver);
+
+            // 检查里程碑状态
+            let milestone = Self::ensure_current_milestone(task_id, milestone_index)?;
+            ensure!(milestone.status == MilestoneStatus::Delivered, Error::<T>::InvalidMilestoneStatus);
+
+            Self::set_milestone_status(task_id, milestone_index, MilestoneStatus::Arbitrating);
+            DisputedMilestone::insert(&task_id, milestone_index);
+
+            Self::deposit_event(RawEvent::MilestoneDisputed(sender.clone(), task_id, milestone_index));
+
+            Self::start_dispute(sender, task_id, owner, evidence)
+        }
+
+        /// 评分 任务结束后创建者为中标者评分（1~5分），每个任务只能评一次
+        #[weight = 10_000]
+        fn rate_task(origin, task_id: u128, rating: u8) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(rating >= 1 && rating <= MAX_RATING, Error::<T>::InvalidRating);
+
+            // 检查任务状态与操作权限
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+            ensure!(RelTaskStatus::get(&task_id) == Some(TaskStatus::Finished), Error::<T>::InvalidTaskStatus);
+            let owner = TaskDetail::<T>::get(&task_id).owner;
+            ensure!(sender == owner, Error::<T>::NotTaskOwner);
+            ensure!(RelTaskReceiver::<T>::contains_key(&task_id), Error::<T>::NoTaskReceiver);
+            ensure!(!TaskRatings::contains_key(&task_id), Error::<T>::AlreadyRated);
+
+            // 团队任务的每个成员都记录该评分
+            let receiver = RelTaskReceiver::<T>::get(&task_id);
+            TaskRatings::insert(&task_id, rating);
+            for (member, _) in Self::task_team(task_id) {
+                Self::update_reputation(&member, |r| {
+                    r.rating_count = r.rating_count.saturating_add(1);
+                    r.rating_total = r.rating_total.saturating_add(rating as u32);
+                });
+            }
+
+            Self::deposit_event(RawEvent::TaskRated(sender, receiver, task_id, rating));
+
+            Ok(())
+        }
+
+        /// 设置自动选标 只有投标中、设置了投标截止区块且尚未收到投标的任务才能设置
+        /// `requirement`为中标所需的信誉要求，为空时取消自动选标
+        #[weight = 10_000]
+        fn set_auto_award(origin, task_id: u128, requirement: Option<ReputationRequirement>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务状态与操作权限
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+            ensure!(sender == TaskDetail::<T>::get(&task_id).owner, Error::<T>::NotTaskOwner);
+            Self::ensure_bidding_open(task_id)?;
+            ensure!(RelTaskBidder::<T>::get(&task_id).is_empty(), Error::<T>::BiddingModeLocked);
+
+            match requirement {
+                Some(requirement) => {
+                    Self::ensure_valid_auto_award(task_id)?;
+                    AutoAward::insert(&task_id, requirement);
+                },
+                None => AutoAward::remove(&task_id),
+            }
+
+            Self::deposit_event(RawEvent::AutoAwardSet(sender, task_id));
+
+            Ok(())
+        }
+
+        /// 设置投标策略 只有投标中的任务才能设置，设置为Open时取消限制
+        #[weight = 10_000]
+        fn set_bid_policy(origin, task_id: u128, policy: BidPolicyOf<T>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务状态与操作权限
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+            ensure!(sender == TaskDetail::<T>::get(&task_id).owner, Error::<T>::NotTaskOwner);
+            ensure!(RelTaskStatus::get(&task_id) == Some(TaskStatus::Bidding), Error::<T>::BidClosed);
+
+            Self::ensure_valid_bid_policy(&policy)?;
+            match policy {
+                BidPolicy::Open => TaskBidPolicy::<T>::remove(&task_id),
+                policy => TaskBidPolicy::<T>::insert(&task_id, policy),
+            }
+
+            Self::deposit_event(RawEvent::BidPolicySet(sender, task_id));
+
+            Ok(())
+        }
+
+        /// 新增任务分类
+        #[weight = 10_000]
+        fn add_category(origin, name: Vec<u8>) -> dispatch::DispatchResult {
+            T::CategoryOrigin::ensure_origin(origin)?;
+
+            Self::ensure_valid_category_name(&name)?;
+
+            let category_id = NextCategoryId::get();
+            let next_category_id = category_id.checked_add(1).ok_or(Error::<T>::CategoryIdOverflow)?;
+            NextCategoryId::put(next_category_id);
+            Categories::insert(category_id, name.clone());
+
+            Self::deposit_event(RawEvent::CategoryAdded(category_id, name));
+
+            Ok(())
+        }
+
+        /// 移除任务分类 已有任务的分类索引保留，新任务不能再使用该分类
+        #[weight = 10_000]
+        fn remove_category(origin, category_id: u32) -> dispatch::DispatchResult {
+            T::CategoryOrigin::ensure_origin(origin)?;
+
+            ensure!(Categories::contains_key(category_id), Error::<T>::NoSuchCategory);
+            Categories::remove(category_id);
+
+            Self::deposit_event(RawEvent::CategoryRemoved(category_id));
+
+            Ok(())
+        }
+
+        /// 注册成为仲裁员
+        #[weight = 10_000]
+        fn register_arbitrator(origin, stake_token: u128) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查质押token是否满足最低要求
+            ensure!(stake_token >= T::MinArbitratorStake::get(), Error::<T>::InvalidStakeToken);
+
+            // 检查是否已经是仲裁员 以及仲裁员池是否已满
+            let mut arbitrators = Arbitrators::<T>::get();
+            ensure!(!arbitrators.contains(&sender), Error::<T>::AlreadyArbitrator);
+            ensure!((arbitrators.len() as u32) < T::MaxArbitrators::get(), Error::<T>::TooManyArbitrators);
+
+            // 质押（锁定）仲裁员token
+            ensure!(T::Currency::can_reserve(&sender, stake_token), Error::<T>::InsufficientBalance);
+            T::Currency::reserve(&sender, stake_token)?;
+
+            arbitrators.push(sender.clone());
+            Arbitrators::<T>::put(arbitrators);
+            ArbitratorStakes::<T>::insert(&sender, stake_token);
+
+            Self::deposit_event(RawEvent::ArbitratorRegistered(sender, stake_token));
+
+            Ok(())
+        }
+
+        /// 退出仲裁员池 退还仲裁员质押
+        #[weight = 10_000]
+        fn unregister_arbitrator(origin) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let mut arbitrators = Arbitrators::<T>::get();
+            let index = arbitrators.iter().position(|a| *a == sender).ok_or(Error::<T>::NotArbitrator)?;
+
+            // 检查是否还有未结的案件 未结案件可先通过recuse_arbitrator回避
+            ensure!(ArbitratorCaseCount::<T>::get(&sender) == 0, Error::<T>::ArbitratorBusy);
+
+            arbitrators.swap_remove(index);
+            Arbitrators::<T>::put(arbitrators);
+
+            let stake_token = ArbitratorStakes::<T>::take(&sender);
+            T::Currency::unreserve(&sender, stake_token);
+
+            Self::deposit_event(RawEvent::ArbitratorUnregistered(sender, stake_token));
+
+            Ok(())
+        }
+
+        /// 发起仲裁 任务创建者或中标者缴纳保证金，随机抽取仲裁小组
+        #[weight = 10_000]
+        #[transactional]
+        fn open_dispute(origin, task_id: u128, evidence: T::Hash) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查任务是否存在
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+
+            // 检查状态变更是否合法
+            let task_status = RelTaskStatus::get(&task_id).ok_or(Error::<T>::InvalidTaskStatus)?;
+            ensure!(task_status.transition_to(TaskStatus::Arbitrating) == Some(StatusActor::Arbitration),
+                Error::<T>::InvalidStatusTransition);
+
+            // 设置了里程碑的任务需针对里程碑发起仲裁
+            ensure!(!TaskMilestones::contains_key(&task_id), Error::<T>::MilestoneTask);
+
+            // 检查操作权限
+            let owner = TaskDetail::<T>::get(&task_id).owner;
+            ensure!((sender == owner || Self::is_team_member(task_id, &sender)), Error::<T>::NotTaskOwnerOrReceiver);
+
+            // 所有子任务结束或撤销后才能发起仲裁 裁决会结算或退还父任务的锁定奖励
+            ensure!(!Self::has_open_subtasks(task_id), Error::<T>::OpenSubTasks);
+
+            Self::start_dispute(sender, task_id, owner, evidence)
+        }
+
+        /// 提交证据
+        #[weight = 10_000]
+        fn submit_evidence(origin, task_id: u128, evidence: T::Hash) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let mut dispute = Disputes::<T>::get(&task_id).ok_or(Error::<T>::NoSuchDispute)?;
+
+            // 检查操作权限
+            let owner = TaskDetail::<T>::get(&task_id).owner;
+            ensure!((sender == owner || Self::is_team_member(task_id, &sender)), Error::<T>::NotTaskOwnerOrReceiver);
+
+            ensure!((dispute.evidence.len() as u32) < T::MaxEvidence::get(), Error::<T>::TooManyEvidence);
+
+            dispute.evidence.push((sender.clone(), evidence));
+            Disputes::<T>::insert(&task_id, dispute);
+
+            Self::deposit_event(RawEvent::EvidenceSubmitted(sender, task_id, evidence));
+
+            Ok(())
+        }
+
+        /// 仲裁员投票 给出中标者应得的任务奖励比例
+        /// 仲裁小组全部投票后以中位数作为裁决，并进入上诉期
+        #[weight = 10_000]
+        #[transactional]
+        fn vote_ruling(origin, task_id: u128, receiver_share: Permill) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let dispute = Disputes::<T>::get(&task_id).ok_or(Error::<T>::NoSuchDispute)?;
+
+            // 检查是否为仲裁小组成员 以及是否已投票
+            ensure!(dispute.panel.contains(&sender), Error::<T>::NotPanelMember);
+            ensure!(!DisputeRulings::<T>::contains_key(&task_id), Error::<T>::AlreadyRuled);
+            ensure!(!DisputeVotes::<T>::contains_key(&task_id, &sender), Error::<T>::AlreadyVoted);
+
+            DisputeVotes::<T>::insert(&task_id, &sender, receiver_share);
+
+            Self::deposit_event(RawEvent::RulingVoted(sender, task_id, receiver_share));
+
+            // 仲裁小组全部投票后给出裁决
+            let voted = dispute.panel.iter().filter(|a| DisputeVotes::<T>::contains_key(&task_id, *a)).count();
+            if voted == dispute.panel.len() {
+                Self::record_ruling(task_id, &dispute)?;
+            }
+
+            Ok(())
+        }
+
+        /// 仲裁员回避 未投票的仲裁小组成员退出案件，从仲裁员池中抽取一名仲裁员替换
+        #[weight = 10_000]
+        #[transactional]
+        fn recuse_arbitrator(origin, task_id: u128) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let mut dispute = Disputes::<T>::get(&task_id).ok_or(Error::<T>::NoSuchDispute)?;
+
+            // 检查是否为仲裁小组成员 以及是否已投票
+            ensure!(dispute.panel.contains(&sender), Error::<T>::NotPanelMember);
+            ensure!(!DisputeRulings::<T>::contains_key(&task_id), Error::<T>::AlreadyRuled);
+            ensure!(!DisputeVotes::<T>::contains_key(&task_id, &sender), Error::<T>::AlreadyVoted);
+
+            let replacement = Self::replace_arbitrator(task_id, &mut dispute, &sender)?;
+            Disputes::<T>::insert(&task_id, dispute);
+
+            Self::deposit_event(RawEvent::ArbitratorReplaced(sender, task_id, replacement));
+
+            Ok(())
+        }
+
+        /// 投票期结束仍未全部投票 任何人都可以调用
+        /// 未投票的仲裁员移出仲裁小组，以已投票的中位数作为裁决；无人投票时争议直接提交理事会判决
+        #[weight = 10_000]
+        #[transactional]
+        fn close_ruling_vote(origin, task_id: u128) -> dispatch::DispatchResult {
+            ensure_signed(origin)?;
+
+            let mut dispute = Disputes::<T>::get(&task_id).ok_or(Error::<T>::NoSuchDispute)?;
+            ensure!(!DisputeRulings::<T>::contains_key(&task_id), Error::<T>::AlreadyRuled);
+            ensure!(<frame_system::Module<T>>::block_number() > dispute.ruling_deadline, Error::<T>::RulingPeriodNotOver);
+
+            Self::remove_absent_arbitrators(task_id, &mut dispute);
+            Disputes::<T>::insert(&task_id, &dispute);
+
+            if dispute.panel.is_empty() {
+                Self::set_task_status(task_id, TaskStatus::Judging);
+                Self::deposit_event(RawEvent::DisputeEscalated(task_id));
+                return Ok(());
+            }
+            Self::record_ruling(task_id, &dispute)
+        }
+
+        /// 对仲裁裁决不服 缴纳上诉费向理事会上诉
+        #[weight = 10_000]
+        #[transactional]
+        fn appeal_ruling(origin, task_id: u128) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            // 检查操作权限
+            ensure!(TaskDetail::<T>::contains_key(&task_id), Error::<T>::NoSuchTask);
+            let owner = TaskDetail::<T>::get(&task_id).owner;
+            ensure!((sender == owner || Self::is_team_member(task_id, &sender)), Error::<T>::NotTaskOwnerOrReceiver);
+
+            // 检查状态变更是否合法
+            let task_status = RelTaskStatus::get(&task_id).ok_or(Error::<T>::InvalidTaskStatus)?;
+            ensure!(task_status.transition_to(TaskStatus::Judging) == Some(StatusActor::Arbitration),
+                Error::<T>::InvalidStatusTransition);
+
+            // 检查是否在上诉期内
+            let (_, appeal_deadline) = DisputeRulings::<T>::get(&task_id).ok_or(Error::<T>::NoRuling)?;
+            ensure!(<frame_system::Module<T>>::block_number() <= appeal_deadline, Error::<T>::AppealPeriodOver);
+
+            // 上诉费转入国库
+            let appeal_fee = T::AppealFee::get();
+            T::Currency::transfer(&sender, &T::TreasuryAccount::get(), appeal_fee)?;
+
+            Self::set_task_status(task_id, TaskStatus::Judging);
+
+            Self::deposit_event(RawEvent::RulingAppealed(sender.clone(), task_id, appeal_fee));
+            Self::deposit_event(RawEvent::TaskStatusUpdated(sender, task_id, TaskStatus::Judging));
+
+            Ok(())
+        }
+
+        /// 上诉期结束且无人上诉 按仲裁裁决执行 任何人都可以调用
+        #[weight = 10_000]
+        #[transactional]
+        fn finalize_ruling(origin, task_id: u128) -> dispatch::DispatchResult {
+            ensure_signed(origin)?;
+
+            let task_status = RelTaskStatus::get(&task_id).ok_or(Error::<T>::NoSuchTask)?;
+            ensure!(task_status == TaskStatus::Arbitrating, Error::<T>::InvalidStatusTransition);
+
+            let (receiver_share, appeal_deadline) = DisputeRulings::<T>::get(&task_id).ok_or(Error::<T>::NoRuling)?;
+            ensure!(<frame_system::Module<T>>::block_number() > appeal_deadline, Error::<T>::AppealPeriodNotOver);
+
+            let dispute = Disputes::<T>::get(&task_id).ok_or(Error::<T>::NoSuchDispute)?;
+            Self::execute_ruling(task_id, dispute, receiver_share)
+        }
+
+        /// 理事会最终判决 覆盖仲裁裁决的奖励拆分比例
+        /// 只能由理事会过半数通过的提案调用
+        #[weight = 10_000]
+        #[transactional]
+        fn final_judgment(origin, task_id: u128, receiver_share: Permill) -> dispatch::DispatchResult {
+            T::JudgeOrigin::ensure_origin(origin)?;
+
+            let task_status = RelTaskStatus::get(&task_id).ok_or(Error::<T>::NoSuchTask)?;
+            ensure!(task_status == TaskStatus::Judging, Error::<T>::InvalidStatusTransition);
+
+            let dispute = Disputes::<T>::get(&task_id).ok_or(Error::<T>::NoSuchDispute)?;
+
+            Self::deposit_event(RawEvent::FinalJudgment(task_id, receiver_share));
+
+            Self::execute_ruling(task_id, dispute, receiver_share)
+        }
+
 		
 	}
 }
+
+impl<T: Config> Module<T> {
+    /// 任务奖励与投标质押的币种
+    pub fn task_currency_id(task_id: u128) -> CurrencyIdOf<T> {
+        TaskCurrency::<T>::get(&task_id).unwrap_or_else(T::GetNativeCurrencyId::get)
+    }
+
+    /// 创建任务 `parent`为空时由创建者锁定任务奖励，否则为子任务，任务奖励从父任务的锁定奖励中划转
+    /// 返回分配的任务ID
+    #[allow(clippy::too_many_arguments)]
+    fn do_create_task(sender: T::AccountId, parent: Option<u128>, currency_id: CurrencyIdOf<T>, stake_token: u128, title: Vec<u8>,
+        content_hash: T::Hash, uri: Vec<u8>, deadline: TaskDeadline<T::BlockNumber>, milestones: Vec<Permill>,
+        client_ref: Option<u128>, category: Option<u32>, tags: Vec<Vec<u8>>) -> Result<u128, DispatchError> {
+        // 分配任务ID
+        let task_id = NextTaskId::get();
+        ensure!(!TaskDetail::<T>::contains_key(&task_id), Error::<T>::TaskAlreadyExisted);
+        let next_task_id = task_id.checked_add(1).ok_or(Error::<T>::TaskIdOverflow)?;
+
+        // 检查进行中的任务数量
+        ensure!(OpenTaskCount::<T>::get(&sender) < T::MaxOpenTasksPerAccount::get(), Error::<T>::TooManyOpenTasks);
+
+        // 检查客户端引用是否重复
+        if let Some(client_ref) = client_ref {
+            ensure!(!TaskByClientRef::<T>::contains_key(&sender, client_ref), Error::<T>::DuplicateClientRef);
+        }
+
+        // 检查质押token数量 必须大于0
+        ensure!(stake_token > 0, Error::<T>::InvalidStakeToken);
+
+        // 检查任务标题与地址，并计算存储押金
+        let deposit = Self::ensure_valid_detail(&title, &uri)?;
+
+        // 检查任务分类与标签，标签计入存储押金
+        let deposit = deposit.saturating_add(Self::ensure_valid_labels(category, &tags)?);
+
+        // 检查任务截止时间
+        Self::ensure_valid_deadline(&deadline)?;
+
+        // 检查里程碑比例
+        Self::ensure_valid_milestones(&milestones)?;
+
+        // 从FRAME系统模块中获取当前区块高度
+        let current_block = <frame_system::Module<T>>::block_number();
+
+        // 初始任务状态
+        let task_status = TaskStatus::Bidding;
+
+        // 检查可用余额是否足够质押任务奖励与存储押金 子任务的奖励从父任务的锁定奖励中划转
+        if parent.is_none() {
+            ensure!(T::MultiCurrency::can_reserve(currency_id, &sender, stake_token), Error::<T>::InsufficientBalance);
+        }
+        ensure!(T::Currency::can_reserve(&sender, deposit), Error::<T>::InsufficientBalance);
+
+        // 质押（锁定）任务奖励与存储押金
+        match parent {
+            Some(parent_id) => Self::fund_subtask(parent_id, &sender, stake_token)?,
+            None => T::MultiCurrency::reserve(currency_id, &sender, stake_token)?,
+        }
+        T::Currency::reserve(&sender, deposit)?;
+
+        // 更新下一个任务ID
+        NextTaskId::put(next_task_id);
+        if currency_id != T::GetNativeCurrencyId::get() {
+            TaskCurrency::<T>::insert(&task_id, currency_id);
+        }
+        if let Some(client_ref) = client_ref {
+            TaskClientRef::insert(&task_id, client_ref);
+            TaskByClientRef::<T>::insert(&sender, client_ref, task_id);
+        }
+
+        // 保存任务详情
+        TaskDetail::<T>::insert(&task_id, TaskInfo {
+            owner: sender.clone(),
+            title,
+            content_hash,
+            uri,
+            created_at: current_block,
+            deposit,
+        });
+        Self::set_task_status(task_id, task_status);
+        RelCreateTaskStake::insert(&task_id, stake_token.clone());
+        Self::schedule_deadlines(task_id, deadline);
+        if !milestones.is_empty() {
+            TaskMilestones::insert(&task_id, Self::split_milestones(stake_token, &milestones));
+        }
+        Self::insert_labels(task_id, category, tags);
+        if let Some(parent_id) = parent {
+            ParentTask::insert(&task_id, parent_id);
+            SubTasks::insert(parent_id, task_id, ());
+            OpenSubTaskCount::mutate(parent_id, |count| *count += 1);
+        }
+
+        // 更新我创建的任务列表
+        CreatedTasks::<T>::insert(&sender, task_id, ());
+        OpenTaskCount::<T>::mutate(&sender, |count| *count += 1);
+
+        // 任务总数+1
+        let task_count = TaskCount::get();
+        match task_count.checked_add(1) { 
+            Some(v)=> { TaskCount::put(v); }, 
+            None => (), 
+        }   
+        
+        // 更新用户列表
+        if !Users::<T>::contains_key(&sender) {
+            Users::<T>::insert(&sender, ());
+            UserCount::mutate(|count| *count = count.saturating_add(1));
+        }
+
+        // 触发创建任务事件 
+        Self::deposit_event(RawEvent::TaskCreated(sender, task_id, task_status, currency_id, stake_token, content_hash, client_ref));
+
+        Ok(task_id)
+    }
+
+    /// 更新任务状态，并维护按状态索引的任务列表
+    /// 任务进入已结束或已撤销状态时，创建者进行中的任务数量-1
+    pub(crate) fn set_task_status(task_id: u128, task_status: TaskStatus) {
+        let old_status = RelTaskStatus::get(&task_id);
+        if let Some(old_status) = old_status {
+            TasksByStatus::remove(old_status, task_id);
+        }
+        TasksByStatus::insert(task_status, task_id, ());
+        RelTaskStatus::insert(&task_id, task_status);
+
+        if old_status.map_or(false, |s| !s.is_closed()) && task_status.is_closed() {
+            let owner = TaskDetail::<T>::get(&task_id).owner;
+            OpenTaskCount::<T>::mutate(&owner, |count| *count = count.saturating_sub(1));
+            Self::close_subtask(task_id);
+        }
+    }
+
+    /// 取出投标人在某任务上的投标质押记录，投标人持有的投标数量-1
+    pub(crate) fn take_bid_stake(task_id: u128, bidder: &T::AccountId) -> Balance {
+        if !RelBidTaskStake::<T>::contains_key(&task_id, bidder) {
+            return 0;
+        }
+        ActiveBidCount::<T>::mutate(bidder, |count| *count = count.saturating_sub(1));
+        BidSequence::<T>::remove(&task_id, bidder);
+        RelBidTaskStake::<T>::take(&task_id, bidder)
+    }
+
+    /// 退还投标人在某任务上的质押，并清除投标质押记录与我投标的任务列表
+    /// 返回实际退还的token数量
+    fn refund_bid(task_id: u128, bidder: &T::AccountId) -> Balance {
+        let stake_token = Self::take_bid_stake(task_id, bidder);
+        T::MultiCurrency::unreserve(Self::task_currency_id(task_id), bidder, stake_token);
+
+        BidTasks::<T>::remove(bidder, task_id);
+        BidProposals::<T>::remove(&task_id, bidder);
+        BidCommitments::<T>::remove(&task_id, bidder);
+
+        stake_token
+    }
+
+    /// 选标：检查中标团队，约定任务条款，保存中标人并退还未中标人的质押
+    /// 调用方需保证在事务中执行，任何一步失败都会整体回滚
+    pub(crate) fn do_delegate(task_id: u128, owner: &T::AccountId, receivers: Vec<(T::AccountId, Permill)>) -> dispatch::DispatchResult {
+        // 检查中标团队 所有成员都必须已投标
+        Self::ensure_valid_team(task_id, &receivers)?;
+        let bidder = receivers[0].0.clone();
+
+        // 中标方案成为任务的约定条款 退还任务奖励与中标报价的差额
+        Self::bind_terms(task_id, owner, &bidder)?;
+
+        // 保存任务的中标人 团队任务同时保存团队成员与比例
+        RelTaskReceiver::<T>::insert(&task_id, bidder.clone());
+        if receivers.len() > 1 {
+            TaskTeam::<T>::insert(&task_id, receivers.clone());
+        }
+
+        // 更新任务状态 进入Doing状态
+        Self::set_task_status(task_id, TaskStatus::Doing);
+
+        // 更新我中标的任务列表
+        for (member, _) in receivers.iter() {
+            ReceivedTasks::<T>::insert(member, task_id, ());
+        }
+        
+        // 退还未中标人的质押、清除其投标质押记录及我投标的任务列表（除中标人外）
+        let task_bidder = RelTaskBidder::<T>::get(&task_id);
+        for loser in task_bidder.iter().filter(|b| !receivers.iter().any(|(member, _)| member == *b)) {
+            let stake_token = Self::refund_bid(task_id, loser);
+            Self::deposit_event(RawEvent::BidRefunded(loser.clone(), task_id, stake_token));
+        }
+
+        // 触发选标任务事件
+        Self::deposit_event(RawEvent::TaskDelegated(owner.clone(), bidder, task_id));
+
+        Ok(())
+    }
+
+    /// 退还任务创建者与所有投标人的质押 子任务的任务奖励退回父任务
+    fn release_stakes(task_id: u128, owner: &T::AccountId) -> dispatch::DispatchResult {
+        // 退还创建者的质押
+        let create_stake = RelCreateTaskStake::take(&task_id);
+        Self::refund_escrow(task_id, owner, create_stake)?;
+
+        // 退还所有投标人的质押
+        for bidder in RelTaskBidder::<T>::get(&task_id).iter() {
+            let stake_token = Self::refund_bid(task_id, bidder);
+            if stake_token > 0 {
+                Self::deposit_event(RawEvent::BidRefunded(bidder.clone(), task_id, stake_token));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 退还任务创建者与所有投标人的质押及存储押金，并清除任务详情及所有任务关系、用户关系信息
+    fn remove_task(task_id: u128, owner: &T::AccountId) -> dispatch::DispatchResult {
+        Self::release_stakes(task_id, owner)?;
+        RelTaskBidder::<T>::remove(&task_id);
+        BidProposals::<T>::remove_prefix(&task_id);
+        BidCommitments::<T>::remove_prefix(&task_id);
+        BidSequence::<T>::remove_prefix(&task_id);
+        AutoAward::remove(&task_id);
+        UnbackedStakes::<T>::remove_prefix(&task_id);
+
+        // 清除中标人关系
+        for (member, _) in Self::task_team(task_id) {
+            ReceivedTasks::<T>::remove(&member, task_id);
+        }
+        RelTaskReceiver::<T>::remove(&task_id);
+        TaskTeam::<T>::remove(&task_id);
+
+        // 退还存储押金，清除任务详情、状态、里程碑与截止时间
+        let detail = TaskDetail::<T>::take(&task_id);
+        T::Currency::unreserve(&detail.owner, detail.deposit);
+        if let Some(client_ref) = TaskClientRef::take(&task_id) {
+            TaskByClientRef::<T>::remove(&detail.owner, client_ref);
+        }
+        if let Some(task_status) = RelTaskStatus::take(&task_id) {
+            TasksByStatus::remove(task_status, task_id);
+            if !task_status.is_closed() {
+                OpenTaskCount::<T>::mutate(owner, |count| *count = count.saturating_sub(1));
+                Self::close_subtask(task_id);
+            }
+        }
+        TaskMilestones::remove(&task_id);
+        TaskBidPolicy::<T>::remove(&task_id);
+        TaskCurrency::<T>::remove(&task_id);
+        Self::remove_labels(task_id);
+        TaskDeliverables::<T>::remove(&task_id);
+        TaskRatings::remove(&task_id);
+        Self::unlink_subtasks(task_id);
+        Self::clear_deadlines(task_id);
+
+        // 更新我创建的任务列表
+        CreatedTasks::<T>::remove(owner, task_id);
+
+        // 任务总数-1
+        TaskCount::mutate(|v| *v = v.saturating_sub(1));
+
+        Ok(())
+    }
+
+    /// 任务结算：将创建者锁定的任务奖励按比例支付给中标团队，并退还每个成员的投标质押
+    /// 调用方需保证在事务中执行，任何一步失败都会整体回滚
+    fn settle_task(task_id: u128, owner: &T::AccountId) -> Result<(Balance, Balance), DispatchError> {
+        // 创建者锁定的任务奖励直接支付给中标人
+        let reward = RelCreateTaskStake::take(&task_id);
+        Self::pay_team(task_id, owner, reward)?;
+
+        // 退还中标人的投标质押
+        let bid_stake = Self::release_team_stakes(task_id);
+
+        // 中标者完成任务数量+1，并铸造任务完成凭证
+        for (member, _) in Self::task_team(task_id) {
+            Self::update_reputation(&member, |r| r.completed = r.completed.saturating_add(1));
+            Self::issue_credential(task_id, owner, &member);
+        }
+
+        let receiver = RelTaskReceiver::<T>::get(&task_id);
+        Self::deposit_event(RawEvent::TaskSettled(owner.clone(), receiver, task_id, reward, bid_stake));
+
+        Ok((reward, bid_stake))
+    }
+}
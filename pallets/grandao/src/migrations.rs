@@ -0,0 +1,277 @@
+// 链上存储迁移
+// 每次存储结构变化时增加一个版本，并在on_runtime_upgrade中按版本依次迁移
+
+use super::*;
+use frame_support::{weights::Weight, traits::Get, IterableStorageMap, IterableStorageDoubleMap,
+    storage::{self, migration::{StorageIterator, take_storage_value}}};
+use sp_std::collections::btree_set::BTreeSet;
+use sp_runtime::traits::Hash;
+
+/// 按存储版本依次执行所有尚未执行的迁移
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight: Weight = T::DbWeight::get().reads(1);
+
+    if StorageVersion::get() == Releases::V1 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+    if StorageVersion::get() == Releases::V2 {
+        weight = weight.saturating_add(migrate_to_v3::<T>());
+    }
+    if StorageVersion::get() == Releases::V3 {
+        weight = weight.saturating_add(migrate_to_v4::<T>());
+    }
+    if StorageVersion::get() == Releases::V4 {
+        weight = weight.saturating_add(migrate_to_v5::<T>());
+    }
+
+    weight
+}
+
+/// V1 -> V2：任务状态由u8改为SCALE编码的TaskStatus
+/// 同时清除旧版本撤销任务后遗留的孤立状态记录，并为旧版本未实际锁定的质押补充锁定
+fn migrate_to_v2<T: Config>() -> Weight {
+    let mut count: Weight = 0;
+
+    RelTaskStatus::translate::<u8, _>(|task_id, status| {
+        count += 1;
+        if !TaskDetail::<T>::contains_key(&task_id) {
+            return None;
+        }
+        TaskStatus::from_u8(status)
+    });
+
+    let weight = reserve_legacy_stakes::<T>().saturating_add(reserve_legacy_bid_stakes::<T>());
+
+    StorageVersion::put(Releases::V2);
+
+    T::DbWeight::get().reads_writes(count.saturating_mul(2), count.saturating_add(1)).saturating_add(weight)
+}
+
+/// 旧版本创建任务时只记录了质押数量，并未实际锁定
+/// 进行中的任务从创建者的可用余额中补充锁定任务奖励；余额不足时质押记录清零，原数量记入UnbackedStakes；
+/// 已结束或已撤销的任务不会再结算，质押记录直接清零。
+/// 避免之后退还或结算时动用其他任务锁定的余额。孤立的质押记录在V5迁移时清除
+fn reserve_legacy_stakes<T: Config>() -> Weight {
+    let currency_id = T::GetNativeCurrencyId::get();
+    let mut reads: Weight = 0;
+    let mut writes: Weight = 0;
+
+    let stakes: Vec<(u128, Balance)> = RelCreateTaskStake::iter().collect();
+    for (task_id, stake) in stakes {
+        reads += 2;
+        if stake == 0 || !TaskDetail::<T>::contains_key(&task_id) {
+            continue;
+        }
+        // 此时任务详情仍是旧版本的(AccountId, TaskDetailJson, BlockNumber)，在V3迁移时才转换为TaskInfo
+        let owner = match legacy_task_owner::<T>(task_id) {
+            Some(owner) => owner,
+            None => continue,
+        };
+        let open = RelTaskStatus::get(&task_id).map_or(false, |s| !s.is_closed());
+        if open && T::MultiCurrency::reserve(currency_id, &owner, stake).is_ok() {
+            writes += 1;
+            continue;
+        }
+
+        RelCreateTaskStake::insert(&task_id, 0);
+        if open {
+            UnbackedStakes::<T>::insert(&task_id, &owner, stake);
+        }
+        writes += 2;
+    }
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// 旧版本投标时同样只记录了投标质押数量，并未实际锁定
+/// 仍持有投标的（投标中任务的投标人、进行中任务的中标人）从投标人的可用余额中补充锁定；
+/// 余额不足时投标质押记录清零，原数量记入UnbackedStakes。
+/// 进行中任务未中标的投标与已结束任务的投标不会再退还，直接删除投标质押记录。孤立的投标记录在V5迁移时清除
+fn reserve_legacy_bid_stakes<T: Config>() -> Weight {
+    let currency_id = T::GetNativeCurrencyId::get();
+    let mut reads: Weight = 0;
+    let mut writes: Weight = 0;
+
+    let stakes: Vec<(u128, T::AccountId, Balance)> = RelBidTaskStake::<T>::iter().collect();
+    for (task_id, bidder, stake) in stakes {
+        reads += 2;
+        if !TaskDetail::<T>::contains_key(&task_id) {
+            continue;
+        }
+        let holding = match RelTaskStatus::get(&task_id) {
+            Some(TaskStatus::Bidding) => true,
+            Some(status) if !status.is_closed() => {
+                reads += 1;
+                RelTaskReceiver::<T>::get(&task_id) == bidder
+            }
+            _ => false,
+        };
+        if !holding {
+            RelBidTaskStake::<T>::remove(&task_id, &bidder);
+            RelTaskBidder::<T>::mutate(&task_id, |bidders| bidders.remove(&bidder));
+            writes += 2;
+            continue;
+        }
+        if stake == 0 || T::MultiCurrency::reserve(currency_id, &bidder, stake).is_ok() {
+            writes += 1;
+            continue;
+        }
+
+        RelBidTaskStake::<T>::insert(&task_id, &bidder, 0);
+        UnbackedStakes::<T>::insert(&task_id, &bidder, stake);
+        writes += 2;
+    }
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// 从旧版本的任务详情(AccountId, TaskDetailJson, BlockNumber)中读取任务创建者
+fn legacy_task_owner<T: Config>(task_id: u128) -> Option<T::AccountId> {
+    storage::unhashed::get::<(T::AccountId, Vec<u8>, T::BlockNumber)>(&TaskDetail::<T>::hashed_key_for(task_id))
+        .map(|(owner, _, _)| owner)
+}
+
+/// V2 -> V3：任务详情由(AccountId, TaskDetailJson, BlockNumber)改为TaskInfo
+/// 旧版本的任务详情JSON不再保存在链上，只保留其哈希作为内容哈希；
+/// 旧任务没有缴纳存储押金，押金记为0，标题与地址为空
+fn migrate_to_v3<T: Config>() -> Weight {
+    let mut count: Weight = 0;
+
+    TaskDetail::<T>::translate::<(T::AccountId, Vec<u8>, T::BlockNumber), _>(|_, (owner, detail, created_at)| {
+        count += 1;
+        Some(TaskInfo {
+            owner,
+            title: Vec::new(),
+            content_hash: T::Hashing::hash(&detail),
+            uri: Vec::new(),
+            created_at,
+            deposit: 0,
+        })
+    });
+
+    StorageVersion::put(Releases::V3);
+
+    T::DbWeight::get().reads_writes(count, count.saturating_add(1))
+}
+
+/// V3 -> V4：任务ID改由NextTaskId分配
+/// 已有任务保留原来由调用方指定的ID，并将其记为创建者的客户端引用，
+/// NextTaskId从已有最大任务ID的下一个开始，避免与已有任务冲突
+fn migrate_to_v4<T: Config>() -> Weight {
+    let mut count: Weight = 0;
+    let mut max_task_id: u128 = 0;
+
+    for (task_id, detail) in TaskDetail::<T>::iter() {
+        count += 1;
+        max_task_id = max_task_id.max(task_id);
+        TaskClientRef::insert(&task_id, task_id);
+        TaskByClientRef::<T>::insert(&detail.owner, task_id, task_id);
+    }
+
+    NextTaskId::put(max_task_id.saturating_add(1).max(NextTaskId::get()));
+    StorageVersion::put(Releases::V4);
+
+    T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_mul(2).saturating_add(2))
+}
+
+/// V4 -> V5：任务列表、用户列表与我的任务列表由BTreeSet改为按键索引的map/double_map
+/// 旧的BTreeSet存储项读出后删除；按状态索引的任务列表、进行中任务数量与持有投标数量从现有任务关系重建。
+/// 旧版本撤销任务时只删除了任务详情，重建时跳过没有任务详情的孤立任务，并清除其遗留的质押与投标关系
+fn migrate_to_v5<T: Config>() -> Weight {
+    const MODULE: &[u8] = b"GrandaoModule";
+    let mut reads: Weight = 0;
+    let mut writes: Weight = 0;
+
+    // 孤立任务遗留的质押记录与投标关系
+    let orphans: Vec<u128> = RelCreateTaskStake::iter()
+        .map(|(task_id, _)| task_id)
+        .chain(RelTaskBidder::<T>::iter().map(|(task_id, _)| task_id))
+        .chain(RelTaskReceiver::<T>::iter().map(|(task_id, _)| task_id))
+        .filter(|task_id| !TaskDetail::<T>::contains_key(task_id))
+        .collect();
+    for task_id in orphans {
+        reads += 1;
+        RelCreateTaskStake::remove(&task_id);
+        RelTaskBidder::<T>::remove(&task_id);
+        RelTaskReceiver::<T>::remove(&task_id);
+        writes += 3;
+    }
+    let orphan_bids: Vec<(u128, T::AccountId)> = RelBidTaskStake::<T>::iter()
+        .filter(|(task_id, _, _)| !TaskDetail::<T>::contains_key(task_id))
+        .map(|(task_id, bidder, _)| (task_id, bidder))
+        .collect();
+    for (task_id, bidder) in orphan_bids {
+        reads += 1;
+        RelBidTaskStake::<T>::remove(&task_id, &bidder);
+        writes += 1;
+    }
+
+    // 任务列表：按任务状态重建
+    let _ = take_storage_value::<BTreeSet<u128>>(MODULE, b"TaskList", &[]);
+    for (task_id, task_status) in RelTaskStatus::iter() {
+        reads += 1;
+        if !TaskDetail::<T>::contains_key(&task_id) {
+            continue;
+        }
+        TasksByStatus::insert(task_status, task_id, ());
+        writes += 1;
+        if !task_status.is_closed() {
+            // 此时任务详情仍是旧版本的(AccountId, TaskDetailJson, BlockNumber)，在V3迁移时才转换为TaskInfo
+        let owner = match legacy_task_owner::<T>(task_id) {
+            Some(owner) => owner,
+            None => continue,
+        };
+            OpenTaskCount::<T>::mutate(&owner, |count| *count += 1);
+            reads += 1;
+            writes += 1;
+        }
+    }
+
+    // 用户列表
+    if let Some(users) = take_storage_value::<BTreeSet<T::AccountId>>(MODULE, b"UserList", &[]) {
+        reads += 1;
+        UserCount::put(users.len() as u64);
+        for user in users {
+            Users::<T>::insert(&user, ());
+            writes += 1;
+        }
+    }
+
+    // 我创建、投标、中标的任务列表 旧存储键为blake2_128_concat(AccountId)
+    for (item, index) in [
+        (&b"MyCreateTasks"[..], 0u8),
+        (&b"MyBidTasks"[..], 1u8),
+        (&b"MyReceiveTasks"[..], 2u8),
+    ].iter() {
+        for (key, tasks) in StorageIterator::<BTreeSet<u128>>::new(MODULE, item).drain() {
+            reads += 1;
+            let account = match key.get(16..).and_then(|mut raw| T::AccountId::decode(&mut raw).ok()) {
+                Some(account) => account,
+                None => continue,
+            };
+            for task_id in tasks {
+                reads += 1;
+                if !TaskDetail::<T>::contains_key(&task_id) {
+                    continue;
+                }
+                match *index {
+                    0 => CreatedTasks::<T>::insert(&account, task_id, ()),
+                    1 => BidTasks::<T>::insert(&account, task_id, ()),
+                    _ => ReceivedTasks::<T>::insert(&account, task_id, ()),
+                }
+                writes += 1;
+            }
+        }
+    }
+
+    // 持有的投标数量 孤立任务的投标记录已清除
+    for (_, bidder, _) in RelBidTaskStake::<T>::iter() {
+        reads += 1;
+        ActiveBidCount::<T>::mutate(&bidder, |count| *count += 1);
+        writes += 1;
+    }
+
+    StorageVersion::put(Releases::V5);
+
+    T::DbWeight::get().reads_writes(reads, writes.saturating_add(1))
+}
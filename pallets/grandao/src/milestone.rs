@@ -0,0 +1,126 @@
+// 任务里程碑
+// 任务创建时可以按顺序设置多个里程碑，每个里程碑占任务奖励的一定比例。
+// 中标者按顺序逐个交付里程碑，创建者验收后只支付该里程碑对应的奖励；
+// 对已交付的里程碑有异议时，可以针对该里程碑发起仲裁，仲裁只拆分该里程碑的奖励。
+// 最后一个里程碑结清后任务结束，退还中标者的投标质押
+
+use super::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// 里程碑状态
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum MilestoneStatus {
+    Pending     = 1, //进行中
+    Delivered   = 2, //已交付
+    Arbitrating = 3, //仲裁中
+    Paid        = 4, //已结清
+}
+
+/// 任务里程碑
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Milestone {
+    /// 占任务奖励的比例
+    pub share: Permill,
+    /// 对应的奖励金额 最后一个里程碑包含按比例拆分后的余数
+    pub amount: Balance,
+    /// 里程碑状态
+    pub status: MilestoneStatus,
+}
+
+impl<T: Config> Module<T> {
+    /// 检查里程碑比例：数量不超过MaxMilestones，每个比例大于0，且总和为100%
+    pub(crate) fn ensure_valid_milestones(shares: &[Permill]) -> dispatch::DispatchResult {
+        if shares.is_empty() {
+            return Ok(());
+        }
+        ensure!(shares.len() as u32 <= T::MaxMilestones::get(), Error::<T>::TooManyMilestones);
+
+        let mut total: u32 = 0;
+        for share in shares.iter() {
+            ensure!(!share.is_zero(), Error::<T>::InvalidMilestones);
+            total = total.saturating_add(share.deconstruct());
+        }
+        ensure!(total == Permill::one().deconstruct(), Error::<T>::InvalidMilestones);
+
+        Ok(())
+    }
+
+    /// 按比例拆分任务奖励 拆分后的余数计入最后一个里程碑
+    pub(crate) fn split_milestones(reward: Balance, shares: &[Permill]) -> Vec<Milestone> {
+        let mut remaining = reward;
+        shares.iter().enumerate().map(|(i, share)| {
+            let amount = if i + 1 == shares.len() { remaining } else { *share * reward };
+            remaining = remaining.saturating_sub(amount);
+            Milestone { share: *share, amount, status: MilestoneStatus::Pending }
+        }).collect()
+    }
+
+    /// 当前里程碑：第一个尚未结清的里程碑
+    pub fn current_milestone(task_id: u128) -> Option<(u32, Milestone)> {
+        TaskMilestones::get(&task_id)
+            .into_iter()
+            .enumerate()
+            .find(|(_, milestone)| milestone.status != MilestoneStatus::Paid)
+            .map(|(index, milestone)| (index as u32, milestone))
+    }
+
+    /// 检查`index`是否为当前里程碑，并返回该里程碑
+    pub(crate) fn ensure_current_milestone(task_id: u128, index: u32) -> Result<Milestone, DispatchError> {
+        ensure!(TaskMilestones::contains_key(&task_id), Error::<T>::NoSuchMilestone);
+        match Self::current_milestone(task_id) {
+            Some((current, milestone)) if current == index => Ok(milestone),
+            _ => Err(Error::<T>::NotCurrentMilestone.into()),
+        }
+    }
+
+    /// 更新里程碑状态
+    pub(crate) fn set_milestone_status(task_id: u128, index: u32, status: MilestoneStatus) {
+        TaskMilestones::mutate(&task_id, |milestones| {
+            if let Some(milestone) = milestones.get_mut(index as usize) {
+                milestone.status = status;
+            }
+        });
+    }
+
+    /// 是否为最后一个里程碑
+    pub(crate) fn is_last_milestone(task_id: u128, index: u32) -> bool {
+        index as usize + 1 == TaskMilestones::decode_len(&task_id).unwrap_or_default()
+    }
+
+    /// 结清里程碑：将该里程碑的奖励按比例支付给中标团队
+    /// 最后一个里程碑结清时按正常流程结算任务，退还中标者的投标质押并结束任务
+    /// 调用方需保证在事务中执行
+    pub(crate) fn pay_milestone(task_id: u128, index: u32, owner: &T::AccountId) -> Result<Balance, DispatchError> {
+        let milestone = TaskMilestones::get(&task_id).get(index as usize).cloned()
+            .ok_or(Error::<T>::NoSuchMilestone)?;
+
+        Self::cancel_review(task_id);
+        Self::set_milestone_status(task_id, index, MilestoneStatus::Paid);
+
+        if Self::is_last_milestone(task_id, index) {
+            let (reward, _) = Self::settle_task(task_id, owner)?;
+            Self::set_task_status(task_id, TaskStatus::Finished);
+            Self::deposit_event(RawEvent::MilestonePaid(task_id, index, reward));
+            return Ok(reward);
+        }
+
+        Self::take_tranche(task_id, milestone.amount)?;
+        Self::pay_team(task_id, owner, milestone.amount)?;
+        Self::set_task_status(task_id, TaskStatus::Doing);
+
+        Self::deposit_event(RawEvent::MilestonePaid(task_id, index, milestone.amount));
+
+        Ok(milestone.amount)
+    }
+
+    /// 从任务奖励的锁定余额中扣除一个里程碑的奖励
+    pub(crate) fn take_tranche(task_id: u128, amount: Balance) -> dispatch::DispatchResult {
+        RelCreateTaskStake::try_mutate(&task_id, |escrow| -> dispatch::DispatchResult {
+            *escrow = escrow.checked_sub(amount).ok_or(Error::<T>::InsufficientEscrow)?;
+            Ok(())
+        })
+    }
+}
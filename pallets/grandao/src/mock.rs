@@ -1,14 +1,30 @@
-use crate as pallet_template;
+use crate as pallet_grandao;
 use sp_core::H256;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::{GenesisBuild, Randomness}};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+	traits::{BlakeTwo256, Hash, IdentityLookup, Zero}, testing::Header, Permill,
 };
 use frame_system as system;
+use orml_traits::parameter_type_with_key;
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+pub type AccountId = u64;
+pub type BlockNumber = u64;
+pub type Balance = u128;
+pub type Amount = i128;
+pub type CurrencyId = u32;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const ARBITRATORS: [AccountId; 3] = [11, 12, 13];
+pub const NATIVE: CurrencyId = 0;
+pub const PROJECT_TOKEN: CurrencyId = 1;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -17,7 +33,10 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		Tokens: orml_tokens::{Module, Storage, Call, Config<T>, Event<T>},
+		Currencies: orml_currencies::{Module, Storage, Call, Event<T>},
+		GrandaoModule: pallet_grandao::{Module, Call, Storage, Config, Event<T>},
 	}
 );
 
@@ -34,28 +53,176 @@ impl system::Config for Test {
 	type Origin = Origin;
 	type Call = Call;
 	type Index = u64;
-	type BlockNumber = u64;
+	type BlockNumber = BlockNumber;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
-	type AccountId = u64;
+	type AccountId = AccountId;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
 	type Event = Event;
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = SS58Prefix;
 }
 
-impl pallet_template::Config for Test {
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type Balance = Balance;
 	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Zero::zero()
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = 0;
+}
+
+impl orml_currencies::Config for Test {
+	type Event = Event;
+	type MultiCurrency = Tokens;
+	type NativeCurrency = orml_currencies::BasicCurrencyAdapter<Test, Balances, Amount, BlockNumber>;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = ();
+}
+
+pub struct TestRandomness;
+impl Randomness<H256> for TestRandomness {
+	fn random(subject: &[u8]) -> H256 {
+		BlakeTwo256::hash(subject)
+	}
+}
+
+parameter_types! {
+	pub const MinArbitratorStake: Balance = 1_000;
+	pub const DisputeBond: Balance = 100;
+	pub const ArbitratorPanelSize: u32 = 3;
+	pub const MaxArbitrators: u32 = 10;
+	pub const MaxEvidence: u32 = 5;
+	pub const AppealFee: Balance = 200;
+	pub const AppealPeriod: BlockNumber = 10;
+	pub const RulingPeriod: BlockNumber = 10;
+	pub const TreasuryAccount: AccountId = 100;
+	pub const MaxDeadlinesPerBlock: u32 = 2;
+	pub const MaxDeadlineRetries: u32 = 2;
+	pub const ReviewPeriod: BlockNumber = 20;
+	pub const MaxMilestones: u32 = 4;
+	pub const MaxTitleLength: u32 = 32;
+	pub const MaxUriLength: u32 = 64;
+	pub const DepositPerByte: Balance = 1;
+	pub const MaxOpenTasksPerAccount: u32 = 2;
+	pub const MaxActiveBidsPerAccount: u32 = 2;
+	pub const MaxBiddersPerTask: u32 = 3;
+	pub const BidWithdrawalFee: Permill = Permill::from_percent(10);
+	pub const MaxTagsPerTask: u32 = 3;
+	pub const MaxTagLength: u32 = 16;
+	pub const MaxTeamSize: u32 = 3;
+	pub const MaxBidAllowlist: u32 = 3;
+	pub const UnrevealedBidPenalty: Permill = Permill::from_percent(50);
+}
+
+impl pallet_grandao::Config for Test {
+	type Event = Event;
+	type Currency = orml_currencies::Currency<Test, GetNativeCurrencyId>;
+	type MultiCurrency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type Randomness = TestRandomness;
+	type MinArbitratorStake = MinArbitratorStake;
+	type DisputeBond = DisputeBond;
+	type ArbitratorPanelSize = ArbitratorPanelSize;
+	type MaxArbitrators = MaxArbitrators;
+	type MaxEvidence = MaxEvidence;
+	type JudgeOrigin = frame_system::EnsureRoot<AccountId>;
+	type AppealFee = AppealFee;
+	type AppealPeriod = AppealPeriod;
+	type RulingPeriod = RulingPeriod;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxDeadlinesPerBlock = MaxDeadlinesPerBlock;
+	type MaxDeadlineRetries = MaxDeadlineRetries;
+	type ReviewPeriod = ReviewPeriod;
+	type MaxMilestones = MaxMilestones;
+	type MaxTitleLength = MaxTitleLength;
+	type MaxUriLength = MaxUriLength;
+	type DepositPerByte = DepositPerByte;
+	type MaxOpenTasksPerAccount = MaxOpenTasksPerAccount;
+	type MaxActiveBidsPerAccount = MaxActiveBidsPerAccount;
+	type MaxBiddersPerTask = MaxBiddersPerTask;
+	type BidWithdrawalFee = BidWithdrawalFee;
+	type CategoryOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTagsPerTask = MaxTagsPerTask;
+	type MaxTagLength = MaxTagLength;
+	type Credentials = MockCredentials;
+	type MaxTeamSize = MaxTeamSize;
+	type NftHolders = MockNftHolders;
+	type MaxBidAllowlist = MaxBidAllowlist;
+	type UnrevealedBidPenalty = UnrevealedBidPenalty;
+}
+
+thread_local! {
+	/// 已铸造的任务完成凭证 (receiver, category, credential)
+	pub static CREDENTIALS: RefCell<Vec<(AccountId, Option<u32>, pallet_grandao::TaskCredential<AccountId, H256>)>> = RefCell::new(Vec::new());
+	/// 持有NFT的账户 (class_id, holder)
+	pub static NFT_HOLDERS: RefCell<Vec<(u32, AccountId)>> = RefCell::new(Vec::new());
+}
+
+/// 记录铸造的凭证，供测试检查
+pub struct MockCredentials;
+impl pallet_grandao::CredentialMinter<AccountId, H256> for MockCredentials {
+	fn mint_credential(receiver: &AccountId, category: Option<u32>, credential: pallet_grandao::TaskCredential<AccountId, H256>)
+		-> frame_support::dispatch::DispatchResult {
+		CREDENTIALS.with(|c| c.borrow_mut().push((*receiver, category, credential)));
+		Ok(())
+	}
+}
+
+/// 按NFT_HOLDERS判断是否持有NFT
+pub struct MockNftHolders;
+impl pallet_grandao::ClassHolder<AccountId> for MockNftHolders {
+	fn holds(class_id: u32, who: &AccountId) -> bool {
+		NFT_HOLDERS.with(|h| h.borrow().contains(&(class_id, *who)))
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(ALICE, 100_000), (BOB, 100_000), (CHARLIE, 100_000), (DAVE, 10),
+			(ARBITRATORS[0], 10_000), (ARBITRATORS[1], 10_000), (ARBITRATORS[2], 10_000),
+		],
+	}.assimilate_storage(&mut t).unwrap();
+	orml_tokens::GenesisConfig::<Test> {
+		endowed_accounts: vec![(ALICE, PROJECT_TOKEN, 10_000), (BOB, PROJECT_TOKEN, 1_000)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
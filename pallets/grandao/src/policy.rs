@@ -0,0 +1,66 @@
+// 投标策略
+// 任务创建者可以在投标中为任务设置投标策略，限制哪些账户可以投标：
+// 开放投标、满足最低信誉要求、指定账户（白名单）、或持有某NFT class的token。
+// 策略只在投标时检查，已投标的投标人不受之后修改策略的影响。
+// 持有NFT的检查通过ClassHolder完成，由运行时接入具体的NFT模块
+
+use super::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// 投标策略
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum BidPolicy<AccountId> {
+    /// 除任务创建者外任何账户都可以投标
+    Open,
+    /// 只有信誉记录满足要求的账户可以投标
+    MinReputation(ReputationRequirement),
+    /// 只有白名单中的账户可以投标
+    Allowlist(Vec<AccountId>),
+    /// 只有持有该NFT class的token的账户可以投标
+    ClassHolders(u32),
+}
+
+impl<AccountId> Default for BidPolicy<AccountId> {
+    fn default() -> Self {
+        BidPolicy::Open
+    }
+}
+
+pub type BidPolicyOf<T> = BidPolicy<<T as frame_system::Config>::AccountId>;
+
+/// 查询账户是否持有某NFT class的token
+pub trait ClassHolder<AccountId> {
+    /// `who`是否持有`class_id`的任意一个token
+    fn holds(class_id: u32, who: &AccountId) -> bool;
+}
+
+/// 不接入NFT模块时，任何账户都不满足持有要求
+impl<AccountId> ClassHolder<AccountId> for () {
+    fn holds(_: u32, _: &AccountId) -> bool {
+        false
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// 检查投标策略：白名单长度不超过MaxBidAllowlist
+    pub(crate) fn ensure_valid_bid_policy(policy: &BidPolicyOf<T>) -> dispatch::DispatchResult {
+        if let BidPolicy::Allowlist(accounts) = policy {
+            ensure!(accounts.len() as u32 <= T::MaxBidAllowlist::get(), Error::<T>::TooManyAllowlisted);
+        }
+        Ok(())
+    }
+
+    /// 检查投标人是否满足任务的投标策略
+    pub(crate) fn ensure_bid_permitted(task_id: u128, bidder: &T::AccountId) -> dispatch::DispatchResult {
+        match TaskBidPolicy::<T>::get(&task_id) {
+            BidPolicy::Open => (),
+            BidPolicy::MinReputation(requirement) =>
+                ensure!(Reputations::<T>::get(bidder).meets(&requirement), Error::<T>::InsufficientReputation),
+            BidPolicy::Allowlist(accounts) => ensure!(accounts.contains(bidder), Error::<T>::NotAllowlisted),
+            BidPolicy::ClassHolders(class_id) => ensure!(T::NftHolders::holds(class_id, bidder), Error::<T>::NotClassHolder),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+// 任务查询
+// 供运行时API（GrandaoApi）与grandao_* RPC使用，只读取存储，不修改任何状态。
+// 列表查询按存储键的顺序分页，每页最多MAX_PAGE_SIZE条
+
+use super::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// 分页查询每页的最大条数
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// 任务视图 汇总任务详情、状态、质押、投标人与中标人
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TaskView<AccountId, Hash, BlockNumber, CurrencyId> {
+    /// 任务ID
+    pub task_id: u128,
+    /// 任务详情
+    pub detail: TaskInfo<AccountId, Hash, BlockNumber>,
+    /// 任务状态
+    pub status: TaskStatus,
+    /// 任务奖励与投标质押的币种
+    pub currency_id: CurrencyId,
+    /// 创建者锁定的任务奖励（尚未支付的部分）
+    pub reward: Balance,
+    /// 投标人及其投标质押与投标方案 (AccountId, Token, BidProposal) 已退还的投标质押为0
+    pub bids: Vec<(AccountId, Balance, Option<BidProposal<BlockNumber, Hash>>)>,
+    /// 中标人 团队任务为团队负责人
+    pub receiver: Option<AccountId>,
+    /// 中标团队及每个成员分配任务奖励的比例 单人中标时为比例100%的中标人
+    pub team: Vec<(AccountId, Permill)>,
+    /// 截止时间
+    pub deadline: TaskDeadline<BlockNumber>,
+    /// 里程碑
+    pub milestones: Vec<Milestone>,
+    /// 客户端引用
+    pub client_ref: Option<u128>,
+    /// 任务分类
+    pub category: Option<u32>,
+    /// 任务标签
+    pub tags: Vec<Vec<u8>>,
+    /// 父任务 不是子任务时为空
+    pub parent: Option<u128>,
+    /// 投标策略
+    pub bid_policy: BidPolicy<AccountId>,
+    /// 密封投标的揭示截止区块 不是密封投标时为空
+    pub reveal_close: Option<BlockNumber>,
+    /// 自动选标中标所需的信誉要求 不是自动选标时为空
+    pub auto_award: Option<ReputationRequirement>,
+}
+
+pub type TaskViewOf<T> = TaskView<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::Hash,
+    <T as frame_system::Config>::BlockNumber, CurrencyIdOf<T>>;
+
+impl<T: Config> Module<T> {
+    /// 查询任务
+    pub fn task_view(task_id: u128) -> Option<TaskViewOf<T>> {
+        let status = RelTaskStatus::get(&task_id)?;
+        let bids = RelTaskBidder::<T>::get(&task_id)
+            .into_iter()
+            .map(|bidder| {
+                let stake = RelBidTaskStake::<T>::get(&task_id, &bidder);
+                let proposal = BidProposals::<T>::get(&task_id, &bidder);
+                (bidder, stake, proposal)
+            })
+            .collect();
+        let receiver = if RelTaskReceiver::<T>::contains_key(&task_id) {
+            Some(RelTaskReceiver::<T>::get(&task_id))
+        } else {
+            None
+        };
+
+        Some(TaskView {
+            task_id,
+            detail: TaskDetail::<T>::get(&task_id),
+            status,
+            currency_id: Self::task_currency_id(task_id),
+            reward: RelCreateTaskStake::get(&task_id),
+            bids,
+            receiver,
+            team: Self::task_team(task_id),
+            deadline: TaskDeadlines::<T>::get(&task_id),
+            milestones: TaskMilestones::get(&task_id),
+            client_ref: TaskClientRef::get(&task_id),
+            category: TaskCategory::get(&task_id),
+            tags: TaskTags::get(&task_id),
+            parent: ParentTask::get(&task_id),
+            bid_policy: TaskBidPolicy::<T>::get(&task_id),
+            reveal_close: SealedBidding::<T>::get(&task_id),
+            auto_award: AutoAward::get(&task_id),
+        })
+    }
+
+    /// 查询某账户创建的任务
+    pub fn tasks_by_owner(owner: T::AccountId, page: u32, page_size: u32) -> Vec<u128> {
+        Self::paginate(CreatedTasks::<T>::iter_prefix(owner).map(|(task_id, _)| task_id), page, page_size)
+    }
+
+    /// 查询某账户投标的任务
+    pub fn tasks_by_bidder(bidder: T::AccountId, page: u32, page_size: u32) -> Vec<u128> {
+        Self::paginate(BidTasks::<T>::iter_prefix(bidder).map(|(task_id, _)| task_id), page, page_size)
+    }
+
+    /// 查询某账户中标的任务
+    pub fn tasks_by_receiver(receiver: T::AccountId, page: u32, page_size: u32) -> Vec<u128> {
+        Self::paginate(ReceivedTasks::<T>::iter_prefix(receiver).map(|(task_id, _)| task_id), page, page_size)
+    }
+
+    /// 查询某状态的任务
+    pub fn tasks_by_status(status: TaskStatus, page: u32, page_size: u32) -> Vec<u128> {
+        Self::paginate(TasksByStatus::iter_prefix(status).map(|(task_id, _)| task_id), page, page_size)
+    }
+
+    /// 查询某分类的任务
+    pub fn tasks_by_category(category: u32, page: u32, page_size: u32) -> Vec<u128> {
+        Self::paginate(TasksByCategory::iter_prefix(category).map(|(task_id, _)| task_id), page, page_size)
+    }
+
+    /// 查询带有某标签的任务
+    pub fn tasks_by_tag(tag: Vec<u8>, page: u32, page_size: u32) -> Vec<u128> {
+        Self::paginate(TasksByTag::iter_prefix(tag).map(|(task_id, _)| task_id), page, page_size)
+    }
+
+    /// 查询某任务的子任务
+    pub fn subtasks(parent_id: u128, page: u32, page_size: u32) -> Vec<u128> {
+        Self::paginate(SubTasks::iter_prefix(parent_id).map(|(task_id, _)| task_id), page, page_size)
+    }
+
+    /// 查询所有任务分类 (CategoryId, Name)
+    pub fn categories() -> Vec<(u32, Vec<u8>)> {
+        Categories::iter().collect()
+    }
+
+    fn paginate(tasks: impl Iterator<Item = u128>, page: u32, page_size: u32) -> Vec<u128> {
+        let page_size = page_size.min(MAX_PAGE_SIZE) as usize;
+        tasks.skip((page as usize).saturating_mul(page_size)).take(page_size).collect()
+    }
+}
@@ -0,0 +1,81 @@
+// 信誉记录
+// 每个账户的信誉记录在任务结算、违约与仲裁裁决执行时自动更新；
+// 任务结束后创建者可以为中标者评分（1~5分）。
+// 任务创建者可以通过投标策略（BidPolicy::MinReputation）设置投标所需的最低信誉要求
+
+use super::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// 评分的最大值
+pub const MAX_RATING: u8 = 5;
+
+/// 账户的信誉记录
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Reputation {
+    /// 作为中标者完成的任务数量
+    pub completed: u32,
+    /// 作为中标者未按期交付（违约）的任务数量
+    pub abandoned: u32,
+    /// 胜诉的仲裁数量
+    pub disputes_won: u32,
+    /// 败诉的仲裁数量
+    pub disputes_lost: u32,
+    /// 收到的评分次数
+    pub rating_count: u32,
+    /// 收到的评分总和
+    pub rating_total: u32,
+}
+
+impl Reputation {
+    /// 平均评分（乘以100，如450表示4.5分），没有评分时为0
+    pub fn average_rating(&self) -> u32 {
+        if self.rating_count == 0 {
+            return 0;
+        }
+        self.rating_total.saturating_mul(100) / self.rating_count
+    }
+
+    /// 是否满足信誉要求
+    pub fn meets(&self, requirement: &ReputationRequirement) -> bool {
+        self.completed >= requirement.min_completed
+            && self.abandoned <= requirement.max_abandoned
+            && self.average_rating() >= requirement.min_average_rating
+    }
+}
+
+/// 投标所需的信誉要求
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReputationRequirement {
+    /// 最少完成的任务数量
+    pub min_completed: u32,
+    /// 最多违约的任务数量
+    pub max_abandoned: u32,
+    /// 最低平均评分（乘以100）
+    pub min_average_rating: u32,
+}
+
+impl<T: Config> Module<T> {
+    /// 更新账户的信誉记录
+    pub(crate) fn update_reputation(who: &T::AccountId, f: impl FnOnce(&mut Reputation)) {
+        Reputations::<T>::mutate(who, f);
+    }
+
+    /// 记录仲裁结果 双方各占一半视为平局，不记录胜负；团队任务的每个成员都记录
+    pub(crate) fn record_dispute_outcome(owner: &T::AccountId, receivers: &[T::AccountId], receiver_share: Permill) {
+        let half = Permill::from_percent(50);
+        if receiver_share < half {
+            Self::update_reputation(owner, |r| r.disputes_won = r.disputes_won.saturating_add(1));
+            for receiver in receivers.iter() {
+                Self::update_reputation(receiver, |r| r.disputes_lost = r.disputes_lost.saturating_add(1));
+            }
+        } else if receiver_share > half {
+            Self::update_reputation(owner, |r| r.disputes_lost = r.disputes_lost.saturating_add(1));
+            for receiver in receivers.iter() {
+                Self::update_reputation(receiver, |r| r.disputes_won = r.disputes_won.saturating_add(1));
+            }
+        }
+    }
+}
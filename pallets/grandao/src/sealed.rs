@@ -0,0 +1,80 @@
+// 密封投标
+// 任务创建者可以在收到投标前将任务设置为密封投标，密封投标分为两个阶段：
+// - 提交阶段（投标截止区块之前）：投标人缴纳投标质押，只提交报价与随机盐的哈希 hash(task_id, bidder, price, salt)；
+//   哈希包含任务ID与投标人，其他投标人无法照抄他人的承诺并在看到揭示后跟投；
+// - 揭示阶段（投标截止区块至揭示截止区块）：投标人公开报价、随机盐与投标方案，哈希一致才视为有效投标。
+// 揭示截止后未揭示的投标按UnrevealedBidPenalty扣除部分投标质押转入国库，其余退还；
+// 创建者只能在揭示截止后选标，揭示截止时没有有效投标的任务自动撤销。
+// 密封投标的任务不能直接投标或修改投标，揭示阶段不能撤回投标
+
+use super::*;
+use sp_runtime::traits::Hash;
+
+impl<T: Config> Module<T> {
+    /// 是否为密封投标的任务
+    pub(crate) fn is_sealed(task_id: u128) -> bool {
+        SealedBidding::<T>::contains_key(&task_id)
+    }
+
+    /// 检查揭示截止区块：必须设置投标截止区块，揭示截止区块晚于投标截止区块，且早于交付截止区块
+    pub(crate) fn ensure_valid_reveal_close(task_id: u128, reveal_close: T::BlockNumber) -> dispatch::DispatchResult {
+        let deadline = TaskDeadlines::<T>::get(&task_id);
+        let bidding_close = deadline.bidding_close.ok_or(Error::<T>::InvalidDeadline)?;
+        ensure!(reveal_close > bidding_close, Error::<T>::InvalidDeadline);
+        if let Some(delivery) = deadline.delivery {
+            ensure!(reveal_close < delivery, Error::<T>::InvalidDeadline);
+        }
+        Ok(())
+    }
+
+    /// 设置或取消密封投标，并更新队列中的揭示截止事件
+    pub(crate) fn schedule_reveal(task_id: u128, reveal_close: Option<T::BlockNumber>) {
+        if let Some(old) = SealedBidding::<T>::take(&task_id) {
+            DeadlineQueue::<T>::remove(old, task_id);
+        }
+        if let Some(reveal_close) = reveal_close {
+            DeadlineQueue::<T>::insert(reveal_close, task_id, DeadlineKind::RevealClose);
+            SealedBidding::<T>::insert(&task_id, reveal_close);
+        }
+    }
+
+    /// 检查任务是否在揭示阶段：投标中，已过投标截止区块且未到揭示截止区块
+    pub(crate) fn ensure_reveal_open(task_id: u128) -> dispatch::DispatchResult {
+        ensure!(Some(TaskStatus::Bidding) == RelTaskStatus::get(&task_id), Error::<T>::BidClosed);
+        let reveal_close = SealedBidding::<T>::get(&task_id).ok_or(Error::<T>::NotSealedTask)?;
+
+        let current_block = <frame_system::Module<T>>::block_number();
+        let bidding_close = TaskDeadlines::<T>::get(&task_id).bidding_close.ok_or(Error::<T>::InvalidDeadline)?;
+        ensure!(current_block >= bidding_close && current_block < reveal_close, Error::<T>::NotRevealPhase);
+
+        Ok(())
+    }
+
+    /// 检查揭示截止区块已过，密封投标的任务才能选标
+    pub(crate) fn ensure_reveal_closed(task_id: u128) -> dispatch::DispatchResult {
+        if let Some(reveal_close) = SealedBidding::<T>::get(&task_id) {
+            ensure!(<frame_system::Module<T>>::block_number() >= reveal_close, Error::<T>::RevealNotClosed);
+        }
+        Ok(())
+    }
+
+    /// 检查揭示的报价与随机盐是否与提交的哈希 hash(task_id, bidder, price, salt) 一致
+    pub(crate) fn ensure_matches_commitment(task_id: u128, bidder: &T::AccountId, price: Balance, salt: &T::Hash) -> dispatch::DispatchResult {
+        let commitment = BidCommitments::<T>::get(&task_id, bidder).ok_or(Error::<T>::NoSuchBidder)?;
+        ensure!(T::Hashing::hash_of(&(task_id, bidder, price, salt)) == commitment, Error::<T>::InvalidReveal);
+        Ok(())
+    }
+
+    /// 罚没所有未揭示的投标：扣除UnrevealedBidPenalty转入国库，退还其余投标质押，并从投标人中移除
+    pub(crate) fn forfeit_unrevealed(task_id: u128) -> dispatch::DispatchResult {
+        let unrevealed: Vec<T::AccountId> = BidCommitments::<T>::iter_prefix(task_id).map(|(bidder, _)| bidder).collect();
+
+        for bidder in unrevealed {
+            let (refunded, penalty) = Self::withdraw_bid_stake(task_id, &bidder, T::UnrevealedBidPenalty::get())?;
+            RelTaskBidder::<T>::mutate(&task_id, |bidders| bidders.remove(&bidder));
+            Self::deposit_event(RawEvent::BidForfeited(bidder, task_id, refunded, penalty));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use codec::{Encode, Decode};
+use sp_runtime::RuntimeDebug;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+// 任务状态
+// 以SCALE编码直接存储，编码值与旧版本u8状态值保持一致
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TaskStatus {
+    Bidding     = 1, //待认领（投标中）
+    Doing       = 2, //进行中
+    UnDone      = 3, //已撤销
+    Delivered   = 4, //已交付
+    Accepted    = 5, //已验收
+    Arbitrating = 6, //仲裁中
+    Judging     = 7, //审判中
+    Finished    = 8, //已结束
+}
+
+// 任务状态变更的操作者
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum StatusActor {
+    Owner,              //只有任务创建者
+    Receiver,           //只有任务中标者
+    OwnerOrReceiver,    //任务创建者或中标者
+    Delegation,         //只能通过选标（delegate_task）完成
+    Arbitration,        //只能通过仲裁流程（open_dispute、appeal_ruling、finalize_ruling、final_judgment）完成
+    Expiry,             //只能在截止时间到达时自动触发
+}
+
+impl TaskStatus {
+    /// 从旧版本存储的u8状态值转换，无效值返回None
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(TaskStatus::Bidding),
+            2 => Some(TaskStatus::Doing),
+            3 => Some(TaskStatus::UnDone),
+            4 => Some(TaskStatus::Delivered),
+            5 => Some(TaskStatus::Accepted),
+            6 => Some(TaskStatus::Arbitrating),
+            7 => Some(TaskStatus::Judging),
+            8 => Some(TaskStatus::Finished),
+            _ => None,
+        }
+    }
+
+    /// 是否为已结束或已撤销的任务
+    pub fn is_closed(&self) -> bool {
+        matches!(self, TaskStatus::UnDone | TaskStatus::Finished)
+    }
+
+    /// 任务状态转换表
+    /// 返回从当前状态变更到`next`所需的操作者，不允许的状态变更返回None
+    ///
+    /// 状态变更的附带操作：
+    /// - Bidding -> UnDone：退还创建者与所有投标人的质押（创建者撤销或投标截止）
+    /// - Doing -> UnDone：交付截止时仍未交付，罚没中标者的投标质押给创建者，退还任务奖励
+    /// - Delivered -> Finished：验收期内创建者既未验收也未发起仲裁，自动验收并结算
+    /// - Delivered -> Accepted：创建者验收，同时结算任务奖励并退还中标人的投标质押，任务直接变更为已完成
+    /// - Accepted -> Finished：旧版本遗留的已验收任务，由创建者或中标者结算
+    /// - Doing|Delivered -> Arbitrating：发起人缴纳保证金，抽取仲裁小组
+    /// - Arbitrating -> Judging：对仲裁裁决不服，缴纳上诉费向理事会上诉
+    /// - Arbitrating -> Finished：上诉期结束后按仲裁裁决拆分任务奖励，罚没败诉方质押
+    /// - Judging -> Finished：按理事会最终判决拆分任务奖励，罚没败诉方质押
+    /// - Arbitrating -> Accepted：仲裁小组裁决前撤回争议，退还保证金；裁决后或上诉后只能按裁决或判决结算
+    /// - Arbitrating|Judging -> Doing：里程碑争议结束（裁决执行或撤回），继续交付后续里程碑
+    pub fn transition_to(&self, next: TaskStatus) -> Option<StatusActor> {
+        use TaskStatus::*;
+
+        match (*self, next) {
+            (Bidding, Doing)            => Some(StatusActor::Delegation),
+            (Bidding, UnDone)           => Some(StatusActor::Owner),
+            (Doing, Delivered)          => Some(StatusActor::Receiver),
+            (Doing, UnDone)             => Some(StatusActor::Expiry),
+            (Doing, Arbitrating)        => Some(StatusActor::Arbitration),
+            (Delivered, Accepted)       => Some(StatusActor::Owner),
+            (Delivered, Arbitrating)    => Some(StatusActor::Arbitration),
+            (Delivered, Finished)       => Some(StatusActor::Expiry), //验收期结束自动验收
+            (Accepted, Finished)        => Some(StatusActor::OwnerOrReceiver),
+            (Arbitrating, Accepted)     => Some(StatusActor::Owner), //创建者撤回争议并验收
+            (Arbitrating, Doing)        => Some(StatusActor::Arbitration),
+            (Arbitrating, Judging)      => Some(StatusActor::Arbitration),
+            (Arbitrating, Finished)     => Some(StatusActor::Arbitration),
+            (Judging, Doing)            => Some(StatusActor::Arbitration),
+            (Judging, Finished)         => Some(StatusActor::Arbitration),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// 子任务
+// 中标者（团队任务为团队负责人）在任务进行中可以将部分工作外包为子任务，子任务由中标者创建并管理，
+// 币种与父任务相同，任务奖励从父任务创建者锁定的任务奖励中划转到中标者名下锁定，父任务的任务奖励相应减少，
+// 结算父任务时只支付剩余的部分。
+// 子任务未支付的奖励（撤销、中标报价低于奖励、违约、仲裁退还）退回父任务；父任务已结束时退还父任务的创建者。
+// 父任务在所有子任务结束或撤销前不能交付、验收、撤销或发起仲裁，未结束的子任务数量记录在OpenSubTaskCount中。
+// 设置了里程碑的任务按里程碑拆分奖励，不能创建子任务
+
+use super::*;
+
+impl<T: Config> Module<T> {
+    /// 检查能否为父任务创建子任务
+    /// 父任务必须进行中且未设置里程碑，只有中标者才能创建；父任务设置了交付截止区块时，子任务的交付截止区块不能晚于父任务
+    pub(crate) fn ensure_can_subcontract(parent_id: u128, sender: &T::AccountId, deadline: &TaskDeadline<T::BlockNumber>) -> dispatch::DispatchResult {
+        let parent_status = RelTaskStatus::get(&parent_id).ok_or(Error::<T>::NoSuchTask)?;
+        ensure!(parent_status == TaskStatus::Doing, Error::<T>::InvalidTaskStatus);
+        ensure!(RelTaskReceiver::<T>::get(&parent_id) == *sender, Error::<T>::NotTaskReceiver);
+        ensure!(!TaskMilestones::contains_key(&parent_id), Error::<T>::MilestoneTask);
+
+        if let Some(parent_delivery) = TaskDeadlines::<T>::get(&parent_id).delivery {
+            let delivery = deadline.delivery.ok_or(Error::<T>::InvalidDeadline)?;
+            ensure!(delivery <= parent_delivery, Error::<T>::InvalidDeadline);
+        }
+
+        Ok(())
+    }
+
+    /// 从父任务的锁定奖励中划转`amount`到子任务创建者名下锁定
+    pub(crate) fn fund_subtask(parent_id: u128, owner: &T::AccountId, amount: Balance) -> dispatch::DispatchResult {
+        Self::take_tranche(parent_id, amount)?;
+
+        let parent_owner = TaskDetail::<T>::get(&parent_id).owner;
+        let unpaid = T::MultiCurrency::repatriate_reserved(Self::task_currency_id(parent_id), &parent_owner, owner, amount,
+            BalanceStatus::Reserved)?;
+        ensure!(unpaid == 0, Error::<T>::InsufficientEscrow);
+
+        Ok(())
+    }
+
+    /// 退还任务创建者锁定的任务奖励中未支付的`amount`
+    /// 子任务退回父任务的锁定奖励，父任务已结束时退还父任务的创建者
+    pub(crate) fn refund_escrow(task_id: u128, owner: &T::AccountId, amount: Balance) -> dispatch::DispatchResult {
+        if amount == 0 {
+            return Ok(());
+        }
+        let currency_id = Self::task_currency_id(task_id);
+
+        let parent_id = match ParentTask::get(&task_id) {
+            Some(parent_id) => parent_id,
+            None => {
+                T::MultiCurrency::unreserve(currency_id, owner, amount);
+                return Ok(());
+            },
+        };
+
+        let parent_owner = TaskDetail::<T>::get(&parent_id).owner;
+        let parent_open = RelTaskStatus::get(&parent_id).map_or(false, |s| !s.is_closed());
+        let status = if parent_open { BalanceStatus::Reserved } else { BalanceStatus::Free };
+        let unpaid = T::MultiCurrency::repatriate_reserved(currency_id, owner, &parent_owner, amount, status)?;
+        ensure!(unpaid == 0, Error::<T>::InsufficientEscrow);
+
+        if parent_open {
+            RelCreateTaskStake::mutate(&parent_id, |escrow| *escrow = escrow.saturating_add(amount));
+        }
+
+        Ok(())
+    }
+
+    /// 是否还有未结束、未撤销的子任务
+    pub(crate) fn has_open_subtasks(parent_id: u128) -> bool {
+        OpenSubTaskCount::get(parent_id) > 0
+    }
+
+    /// 子任务结束、撤销或被清除时，父任务未结束的子任务数量-1
+    pub(crate) fn close_subtask(task_id: u128) {
+        if let Some(parent_id) = ParentTask::get(&task_id) {
+            OpenSubTaskCount::mutate(parent_id, |count| *count = count.saturating_sub(1));
+        }
+    }
+
+    /// 清除任务的父子任务关系
+    pub(crate) fn unlink_subtasks(task_id: u128) {
+        if let Some(parent_id) = ParentTask::take(&task_id) {
+            SubTasks::remove(parent_id, task_id);
+        }
+        OpenSubTaskCount::remove(task_id);
+        for (child_id, _) in SubTasks::drain_prefix(task_id) {
+            ParentTask::remove(&child_id);
+        }
+    }
+}
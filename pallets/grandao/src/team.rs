@@ -0,0 +1,83 @@
+// 团队任务
+// 选标时可以选择多个投标人组成团队共同完成任务，每个成员按Permill比例分配任务奖励。
+// RelTaskReceiver保存团队的第一个成员（负责人），中标方案按负责人的投标方案约定；
+// 团队任何成员都可以交付任务与里程碑、提交交付物、发起仲裁。
+// 结算时按比例拆分任务奖励（余数归最后一个成员），并退还每个成员各自的投标质押；违约或败诉时罚没所有成员的投标质押。
+// 单人中标的任务不记录TaskTeam，视为比例为100%的单人团队
+
+use super::*;
+
+impl<T: Config> Module<T> {
+    /// 检查团队：人数不超过MaxTeamSize，成员不重复且都已投标，每个比例大于0且总和为100%
+    pub(crate) fn ensure_valid_team(task_id: u128, team: &[(T::AccountId, Permill)]) -> dispatch::DispatchResult {
+        ensure!(!team.is_empty(), Error::<T>::InvalidTeam);
+        ensure!(team.len() as u32 <= T::MaxTeamSize::get(), Error::<T>::TooManyReceivers);
+
+        let bidders = RelTaskBidder::<T>::get(&task_id);
+        let mut members = BTreeSet::new();
+        let mut total: u32 = 0;
+        for (member, share) in team.iter() {
+            ensure!(bidders.contains(member), Error::<T>::NoSuchBidder);
+            ensure!(members.insert(member), Error::<T>::InvalidTeam);
+            ensure!(!share.is_zero(), Error::<T>::InvalidTeam);
+            total = total.saturating_add(share.deconstruct());
+        }
+        ensure!(total == Permill::one().deconstruct(), Error::<T>::InvalidTeam);
+
+        Ok(())
+    }
+
+    /// 任务的中标团队 (AccountId, Share) 单人中标时为比例100%的负责人，尚未选标时为空
+    pub fn task_team(task_id: u128) -> Vec<(T::AccountId, Permill)> {
+        if TaskTeam::<T>::contains_key(&task_id) {
+            return TaskTeam::<T>::get(&task_id);
+        }
+        if RelTaskReceiver::<T>::contains_key(&task_id) {
+            return sp_std::vec![(RelTaskReceiver::<T>::get(&task_id), Permill::one())];
+        }
+        Vec::new()
+    }
+
+    /// 是否为任务中标团队的成员
+    pub(crate) fn is_team_member(task_id: u128, who: &T::AccountId) -> bool {
+        Self::task_team(task_id).iter().any(|(member, _)| member == who)
+    }
+
+    /// 将创建者锁定的`amount`按比例支付给团队成员，余数归最后一个成员
+    pub(crate) fn pay_team(task_id: u128, owner: &T::AccountId, amount: Balance) -> dispatch::DispatchResult {
+        let currency_id = Self::task_currency_id(task_id);
+        let team = Self::task_team(task_id);
+
+        let mut remaining = amount;
+        for (i, (member, share)) in team.iter().enumerate() {
+            let paid = if i + 1 == team.len() { remaining } else { *share * amount };
+            remaining = remaining.saturating_sub(paid);
+            let unpaid = T::MultiCurrency::repatriate_reserved(currency_id, owner, member, paid, BalanceStatus::Free)?;
+            ensure!(unpaid == 0, Error::<T>::InsufficientEscrow);
+        }
+
+        Ok(())
+    }
+
+    /// 退还所有团队成员的投标质押 返回退还的总数
+    pub(crate) fn release_team_stakes(task_id: u128) -> Balance {
+        let currency_id = Self::task_currency_id(task_id);
+        Self::task_team(task_id).iter().fold(0, |total, (member, _)| {
+            let bid_stake = Self::take_bid_stake(task_id, member);
+            T::MultiCurrency::unreserve(currency_id, member, bid_stake);
+            total.saturating_add(bid_stake)
+        })
+    }
+
+    /// 将所有团队成员的投标质押罚没给任务创建者 返回 (成员, 罚没数量)
+    pub(crate) fn slash_team_stakes(task_id: u128, owner: &T::AccountId) -> Result<Vec<(T::AccountId, Balance)>, DispatchError> {
+        let currency_id = Self::task_currency_id(task_id);
+        let mut slashed = Vec::new();
+        for (member, _) in Self::task_team(task_id) {
+            let bid_stake = Self::take_bid_stake(task_id, &member);
+            T::MultiCurrency::repatriate_reserved(currency_id, &member, owner, bid_stake, BalanceStatus::Free)?;
+            slashed.push((member, bid_stake));
+        }
+        Ok(slashed)
+    }
+}
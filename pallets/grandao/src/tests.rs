@@ -1,2 +1,1036 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Error, TaskStatus, TaskDeadline, MilestoneStatus, mock::*};
+use frame_support::{assert_ok, assert_noop, StorageMap, StorageDoubleMap};
+use frame_support::traits::OnInitialize;
+use sp_runtime::Permill;
+use sp_core::H256;
+use orml_traits::{BasicCurrency, BasicReservableCurrency, MultiCurrency, MultiReservableCurrency};
+
+type NativeCurrency = <Test as crate::Config>::Currency;
+
+const TITLE: &[u8] = b"task";
+const CID: &[u8] = b"ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+fn deposit() -> u128 {
+	(TITLE.len() + CID.len()) as u128 * DepositPerByte::get()
+}
+
+fn create_task(owner: AccountId, stake_token: u128) -> frame_support::dispatch::DispatchResult {
+	GrandaoModule::create_task(Origin::signed(owner), NATIVE, stake_token, TITLE.to_vec(), Default::default(), CID.to_vec(),
+		Default::default(), Vec::new(), None, None, Vec::new())
+}
+
+/// 按任务奖励全额报价投标，预计交付区块为任务的交付截止区块（未设置时为1_000）
+fn bid(bidder: AccountId, task_id: u128, stake_token: u128) -> frame_support::dispatch::DispatchResult {
+	let price = GrandaoModule::rel_create_task_stake(task_id);
+	let delivery = GrandaoModule::task_deadlines(task_id).delivery.unwrap_or(1_000);
+	GrandaoModule::bid_task(Origin::signed(bidder), task_id, stake_token, price, delivery, Default::default())
+}
+
+fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		GrandaoModule::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn create_task_reserves_stake() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 1_000 + deposit());
+		assert_eq!(NativeCurrency::free_balance(&ALICE), 99_000 - deposit());
+	});
+}
+
+#[test]
+fn create_task_fails_without_enough_balance() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			create_task(DAVE, 1_000),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn delegate_task_refunds_losing_bids() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(bid(CHARLIE, 1, 60));
+		assert_eq!(NativeCurrency::reserved_balance(&CHARLIE), 60);
+
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 50);
+		assert_eq!(NativeCurrency::reserved_balance(&CHARLIE), 0);
+		assert!(!crate::RelBidTaskStake::<Test>::contains_key(1, CHARLIE));
+		assert!(!crate::BidTasks::<Test>::contains_key(CHARLIE, 1));
+		assert_eq!(GrandaoModule::active_bid_count(CHARLIE), 0);
+	});
+}
+
+#[test]
+fn finishing_task_pays_receiver_and_returns_bid_stake() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted));
+
+		// 验收即结算
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Finished));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), deposit());
+		assert_eq!(NativeCurrency::free_balance(&ALICE), 99_000 - deposit());
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+		assert_eq!(NativeCurrency::free_balance(&BOB), 101_000);
+
+		// 清除已结束的任务 退还存储押金
+		assert_noop!(GrandaoModule::remove_closed_task(Origin::signed(BOB), 1), Error::<Test>::NotTaskOwner);
+		assert_ok!(GrandaoModule::remove_closed_task(Origin::signed(ALICE), 1));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 0);
+		assert_eq!(NativeCurrency::free_balance(&ALICE), 99_000);
+		assert!(!crate::ReceivedTasks::<Test>::contains_key(BOB, 1));
+		assert!(!crate::TasksByStatus::contains_key(TaskStatus::Finished, 1));
+	});
+}
+
+#[test]
+fn revoke_task_refunds_everyone_and_clears_storage() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+
+		assert_ok!(GrandaoModule::revoke_task(Origin::signed(ALICE), 1));
+
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 0);
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+		assert!(!crate::CreatedTasks::<Test>::contains_key(ALICE, 1));
+		assert!(!crate::BidTasks::<Test>::contains_key(BOB, 1));
+		assert!(GrandaoModule::rel_task_bidder(1).is_empty());
+		assert!(!crate::TasksByStatus::contains_key(TaskStatus::Bidding, 1));
+		assert_eq!(GrandaoModule::open_task_count(ALICE), 0);
+		assert_eq!(GrandaoModule::task_count(), 0);
+	});
+}
+
+#[test]
+fn revoke_task_fails_once_doing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		assert_noop!(GrandaoModule::revoke_task(Origin::signed(ALICE), 1), Error::<Test>::RevokeClosed);
+	});
+}
+
+#[test]
+fn remove_closed_task_requires_closed_status() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_noop!(GrandaoModule::remove_closed_task(Origin::signed(ALICE), 1), Error::<Test>::TaskNotClosed);
+	});
+}
+
+#[test]
+fn update_task_status_follows_transition_table() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_noop!(
+			GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Finished),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_noop!(
+			GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Doing),
+			Error::<Test>::DelegateRequired
+		);
+
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_noop!(
+			GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_noop!(
+			GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Delivered),
+			Error::<Test>::NotTaskReceiver
+		);
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Delivered));
+	});
+}
+
+#[test]
+fn arbitration_splits_reward_and_slashes_losing_plaintiff() {
+	new_test_ext().execute_with(|| {
+		for arbitrator in ARBITRATORS.iter() {
+			assert_ok!(GrandaoModule::register_arbitrator(Origin::signed(*arbitrator), 1_000));
+		}
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+
+		assert_ok!(GrandaoModule::open_dispute(Origin::signed(ALICE), 1, Default::default()));
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Arbitrating));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 1_100 + deposit());
+
+		let shares = [70, 80, 90];
+		for (arbitrator, share) in ARBITRATORS.iter().zip(shares.iter()) {
+			assert_ok!(GrandaoModule::vote_ruling(Origin::signed(*arbitrator), 1, Permill::from_percent(*share)));
+		}
+
+		// 裁决后进入上诉期 创建者不能再撤回争议
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Arbitrating));
+		assert_noop!(
+			GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted),
+			Error::<Test>::AlreadyRuled
+		);
+		assert_noop!(GrandaoModule::finalize_ruling(Origin::signed(CHARLIE), 1), Error::<Test>::AppealPeriodNotOver);
+		System::set_block_number(1 + AppealPeriod::get() + 1);
+		assert_ok!(GrandaoModule::finalize_ruling(Origin::signed(CHARLIE), 1));
+
+		// 中位数80%支付给中标者，其余退还创建者；创建者败诉，保证金平分给仲裁小组
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Finished));
+		assert_eq!(NativeCurrency::free_balance(&BOB), 100_800);
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), deposit());
+		assert_eq!(NativeCurrency::free_balance(&ALICE), 100_000 - 800 - 100 - deposit());
+		assert_eq!(GrandaoModule::arbitrator_case_count(ARBITRATORS[0]), 0);
+		assert!(GrandaoModule::disputes(1).is_none());
+	});
+}
+
+#[test]
+fn locked_defendant_does_not_block_dispute() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::{LockableCurrency, WithdrawReasons};
+
+		for arbitrator in ARBITRATORS.iter() {
+			assert_ok!(GrandaoModule::register_arbitrator(Origin::signed(*arbitrator), 1_000));
+		}
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		// 任务创建者的可用余额全部被锁仓，无法锁定被诉方保证金
+		Balances::set_lock(*b"grandaot", &ALICE, u128::MAX, WithdrawReasons::all());
+		assert_ok!(GrandaoModule::open_dispute(Origin::signed(BOB), 1, Default::default()));
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Arbitrating));
+		assert_eq!(GrandaoModule::disputes(1).unwrap().defendant_bond, 0);
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 1_000 + deposit());
+	});
+}
+
+#[test]
+fn council_judgment_overrides_arbitration_ruling() {
+	new_test_ext().execute_with(|| {
+		for arbitrator in ARBITRATORS.iter() {
+			assert_ok!(GrandaoModule::register_arbitrator(Origin::signed(*arbitrator), 1_000));
+		}
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::open_dispute(Origin::signed(BOB), 1, Default::default()));
+		// 被诉的任务创建者同时锁定保证金
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 1_100 + deposit());
+		for arbitrator in ARBITRATORS.iter() {
+			assert_ok!(GrandaoModule::vote_ruling(Origin::signed(*arbitrator), 1, Permill::zero()));
+		}
+
+		assert_ok!(GrandaoModule::appeal_ruling(Origin::signed(BOB), 1));
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Judging));
+		assert_noop!(
+			GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_eq!(NativeCurrency::free_balance(&TreasuryAccount::get()), 200);
+
+		assert_noop!(
+			GrandaoModule::final_judgment(Origin::signed(ALICE), 1, Permill::one()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(GrandaoModule::final_judgment(Origin::root(), 1, Permill::one()));
+
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Finished));
+		assert_eq!(NativeCurrency::free_balance(&BOB), 100_000 + 1_000 - 200);
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+
+		// 任务创建者败诉，其保证金平分给仲裁小组
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), deposit());
+		assert_eq!(NativeCurrency::free_balance(&ALICE), 100_000 - 1_000 - 100 - deposit());
+		let panel_balance: u128 = ARBITRATORS.iter().map(|a| NativeCurrency::free_balance(a)).sum();
+		assert_eq!(panel_balance, 3 * (10_000 - 1_000) + 100);
+	});
+}
+
+#[test]
+fn ruling_vote_times_out_without_absent_arbitrators() {
+	new_test_ext().execute_with(|| {
+		for arbitrator in ARBITRATORS.iter() {
+			assert_ok!(GrandaoModule::register_arbitrator(Origin::signed(*arbitrator), 1_000));
+		}
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::open_dispute(Origin::signed(ALICE), 1, Default::default()));
+		assert_ok!(GrandaoModule::vote_ruling(Origin::signed(ARBITRATORS[0]), 1, Permill::from_percent(60)));
+		assert_ok!(GrandaoModule::vote_ruling(Origin::signed(ARBITRATORS[1]), 1, Permill::from_percent(80)));
+
+		assert_noop!(GrandaoModule::close_ruling_vote(Origin::signed(CHARLIE), 1), Error::<Test>::RulingPeriodNotOver);
+		System::set_block_number(1 + RulingPeriod::get() + 1);
+		assert_ok!(GrandaoModule::close_ruling_vote(Origin::signed(CHARLIE), 1));
+
+		// 未投票的仲裁员移出仲裁小组并可以退出仲裁员池，以已投票的中位数裁决
+		assert_eq!(GrandaoModule::disputes(1).unwrap().panel, vec![ARBITRATORS[0], ARBITRATORS[1]]);
+		assert_eq!(GrandaoModule::dispute_rulings(1).map(|(share, _)| share), Some(Permill::from_percent(60)));
+		assert_ok!(GrandaoModule::unregister_arbitrator(Origin::signed(ARBITRATORS[2])));
+	});
+}
+
+#[test]
+fn ruling_vote_without_votes_escalates_to_council() {
+	new_test_ext().execute_with(|| {
+		for arbitrator in ARBITRATORS.iter() {
+			assert_ok!(GrandaoModule::register_arbitrator(Origin::signed(*arbitrator), 1_000));
+		}
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::open_dispute(Origin::signed(BOB), 1, Default::default()));
+
+		System::set_block_number(1 + RulingPeriod::get() + 1);
+		assert_ok!(GrandaoModule::close_ruling_vote(Origin::signed(CHARLIE), 1));
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Judging));
+
+		assert_ok!(GrandaoModule::final_judgment(Origin::root(), 1, Permill::one()));
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Finished));
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+	});
+}
+
+#[test]
+fn busy_arbitrator_recuses_and_is_replaced() {
+	new_test_ext().execute_with(|| {
+		for arbitrator in ARBITRATORS.iter() {
+			assert_ok!(GrandaoModule::register_arbitrator(Origin::signed(*arbitrator), 1_000));
+		}
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::open_dispute(Origin::signed(ALICE), 1, Default::default()));
+
+		// 没有可替换的仲裁员
+		assert_noop!(GrandaoModule::recuse_arbitrator(Origin::signed(ARBITRATORS[0]), 1), Error::<Test>::NotEnoughArbitrators);
+		assert_noop!(GrandaoModule::unregister_arbitrator(Origin::signed(ARBITRATORS[0])), Error::<Test>::ArbitratorBusy);
+
+		assert_ok!(GrandaoModule::register_arbitrator(Origin::signed(CHARLIE), 1_000));
+		assert_ok!(GrandaoModule::recuse_arbitrator(Origin::signed(ARBITRATORS[0]), 1));
+		assert!(GrandaoModule::disputes(1).unwrap().panel.contains(&CHARLIE));
+		assert_eq!(GrandaoModule::arbitrator_case_count(CHARLIE), 1);
+		assert_ok!(GrandaoModule::unregister_arbitrator(Origin::signed(ARBITRATORS[0])));
+	});
+}
+
+#[test]
+fn bidding_deadline_expires_task_and_refunds() {
+	new_test_ext().execute_with(|| {
+		let deadline = TaskDeadline { bidding_close: Some(5), delivery: None, review_window: None };
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, Vec::new(), None, None, Vec::new()));
+		assert_ok!(bid(BOB, 1, 50));
+
+		run_to_block(5);
+
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::UnDone));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), deposit());
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+	});
+}
+
+#[test]
+fn missed_delivery_slashes_receiver_stake() {
+	new_test_ext().execute_with(|| {
+		let deadline = TaskDeadline { bidding_close: None, delivery: Some(5), review_window: None };
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, Vec::new(), None, None, Vec::new()));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		run_to_block(5);
+
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::UnDone));
+		assert_eq!(NativeCurrency::free_balance(&ALICE), 100_050 - deposit());
+		assert_eq!(NativeCurrency::free_balance(&BOB), 99_950);
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+	});
+}
+
+#[test]
+fn delivery_deadline_is_postponed_while_milestone_awaits_review() {
+	new_test_ext().execute_with(|| {
+		let deadline = TaskDeadline { bidding_close: None, delivery: Some(5), review_window: None };
+		let shares = vec![Permill::from_percent(30), Permill::from_percent(70)];
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, shares, None, None, Vec::new()));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::deliver_milestone(Origin::signed(BOB), 1, 0));
+
+		// 里程碑等待验收 交付截止顺延一个验收期
+		run_to_block(5);
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Doing));
+		assert_eq!(GrandaoModule::task_deadlines(1).delivery, Some(5 + ReviewPeriod::get()));
+
+		run_to_block(21);
+		assert_eq!(GrandaoModule::task_milestones(1)[0].status, MilestoneStatus::Paid);
+
+		// 顺延后仍未交付下一个里程碑 视为违约
+		run_to_block(5 + ReviewPeriod::get());
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::UnDone));
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+	});
+}
+
+#[test]
+fn delivery_deadline_is_postponed_during_arbitration() {
+	new_test_ext().execute_with(|| {
+		for arbitrator in ARBITRATORS.iter() {
+			assert_ok!(GrandaoModule::register_arbitrator(Origin::signed(*arbitrator), 1_000));
+		}
+		let deadline = TaskDeadline { bidding_close: None, delivery: Some(5), review_window: None };
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, Vec::new(), None, None, Vec::new()));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::open_dispute(Origin::signed(BOB), 1, Default::default()));
+
+		run_to_block(5);
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Arbitrating));
+		assert_eq!(GrandaoModule::task_deadlines(1).delivery, Some(5 + ReviewPeriod::get()));
+		assert_eq!(GrandaoModule::deadline_queue(5 + ReviewPeriod::get(), 1), Some(crate::DeadlineKind::Delivery));
+	});
+}
+
+#[test]
+fn delivered_task_is_auto_accepted_after_review_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+
+		run_to_block(20);
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Delivered));
+
+		run_to_block(21);
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Finished));
+		assert_eq!(GrandaoModule::review_deadline(1), None);
+		assert_eq!(NativeCurrency::free_balance(&ALICE), 99_000 - deposit());
+		assert_eq!(NativeCurrency::free_balance(&BOB), 101_000);
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+	});
+}
+
+#[test]
+fn failing_deadline_is_dropped_after_max_retries() {
+	new_test_ext().execute_with(|| {
+		use frame_support::IterableStorageDoubleMap;
+
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+
+		// 创建者锁定的任务奖励被解除，自动验收结算失败
+		NativeCurrency::unreserve(&ALICE, NativeCurrency::reserved_balance(&ALICE));
+
+		// 验收截止后重试MaxDeadlineRetries次
+		run_to_block(20 + MaxDeadlineRetries::get() as u64);
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Delivered));
+		assert_eq!(GrandaoModule::deadline_retries(1, crate::DeadlineKind::Review), MaxDeadlineRetries::get());
+		assert_eq!(GrandaoModule::review_deadline(1), Some(21 + MaxDeadlineRetries::get() as u64));
+
+		run_to_block(21 + MaxDeadlineRetries::get() as u64);
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Delivered));
+		assert_eq!(GrandaoModule::review_deadline(1), None);
+		assert_eq!(GrandaoModule::deadline_retries(1, crate::DeadlineKind::Review), 0);
+		assert!(!crate::DeadlineQueue::<Test>::iter().any(|(_, task_id, kind)| task_id == 1 && kind == crate::DeadlineKind::Review));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::pallet_grandao(crate::RawEvent::DeadlineDropped(1, crate::DeadlineKind::Review))));
+	});
+}
+
+#[test]
+fn accepting_milestones_pays_each_tranche() {
+	new_test_ext().execute_with(|| {
+		let shares = vec![Permill::from_percent(30), Permill::from_percent(70)];
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), Default::default(), shares, None, None, Vec::new()));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		assert_noop!(
+			GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered),
+			Error::<Test>::MilestoneTask
+		);
+		assert_noop!(
+			GrandaoModule::deliver_milestone(Origin::signed(BOB), 1, 1),
+			Error::<Test>::NotCurrentMilestone
+		);
+
+		assert_ok!(GrandaoModule::deliver_milestone(Origin::signed(BOB), 1, 0));
+		assert_ok!(GrandaoModule::accept_milestone(Origin::signed(ALICE), 1, 0));
+		assert_eq!(GrandaoModule::task_milestones(1)[0].status, MilestoneStatus::Paid);
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Doing));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 700 + deposit());
+		assert_eq!(NativeCurrency::free_balance(&BOB), 100_300 - 50);
+
+		assert_ok!(GrandaoModule::deliver_milestone(Origin::signed(BOB), 1, 1));
+		assert_ok!(GrandaoModule::accept_milestone(Origin::signed(ALICE), 1, 1));
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Finished));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), deposit());
+		assert_eq!(NativeCurrency::free_balance(&BOB), 101_000);
+	});
+}
+
+#[test]
+fn milestone_shares_must_add_up() {
+	new_test_ext().execute_with(|| {
+		let shares = vec![Permill::from_percent(30), Permill::from_percent(60)];
+		assert_noop!(
+			GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), Default::default(), shares, None, None, Vec::new()),
+			Error::<Test>::InvalidMilestones
+		);
+	});
+}
+
+#[test]
+fn create_task_checks_detail_limits() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, vec![b'a'; 33], Default::default(), CID.to_vec(),
+				Default::default(), Vec::new(), None, None, Vec::new()),
+			Error::<Test>::TitleTooLong
+		);
+		assert_noop!(
+			GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), b"https://example.com".to_vec(),
+				Default::default(), Vec::new(), None, None, Vec::new()),
+			Error::<Test>::InvalidTaskUri
+		);
+		assert!(crate::is_valid_cid(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"));
+	});
+}
+
+#[test]
+fn task_ids_are_assigned_by_chain() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(GrandaoModule::create_task(Origin::signed(BOB), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(),
+			Default::default(), Vec::new(), Some(42), None, Vec::new()));
+		assert_eq!(GrandaoModule::next_task_id(), 3);
+		assert_eq!(GrandaoModule::task_detail(2).owner, BOB);
+		assert_eq!(GrandaoModule::task_by_client_ref(BOB, 42), Some(2));
+
+		assert_noop!(
+			GrandaoModule::create_task(Origin::signed(BOB), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(),
+				Default::default(), Vec::new(), Some(42), None, Vec::new()),
+			Error::<Test>::DuplicateClientRef
+		);
+	});
+}
+
+#[test]
+fn open_tasks_per_account_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_noop!(create_task(ALICE, 1_000), Error::<Test>::TooManyOpenTasks);
+		assert!(crate::TasksByStatus::contains_key(TaskStatus::Bidding, 2));
+
+		// 撤销后释放名额
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::UnDone));
+		assert!(crate::TasksByStatus::contains_key(TaskStatus::UnDone, 1));
+		assert!(!crate::TasksByStatus::contains_key(TaskStatus::Bidding, 1));
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_eq!(GrandaoModule::open_task_count(ALICE), 2);
+	});
+}
+
+#[test]
+fn bidders_per_task_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(bid(CHARLIE, 1, 50));
+		assert_ok!(bid(ARBITRATORS[0], 1, 50));
+		assert_noop!(bid(ARBITRATORS[1], 1, 50), Error::<Test>::TooManyBidders);
+
+		// 撤回投标后释放名额
+		assert_ok!(GrandaoModule::withdraw_bid(Origin::signed(CHARLIE), 1));
+		assert_ok!(bid(ARBITRATORS[1], 1, 50));
+	});
+}
+
+#[test]
+fn task_view_and_paginated_lists() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(create_task(ALICE, 2_000));
+		assert_ok!(bid(BOB, 2, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 2));
+
+		let view = GrandaoModule::task_view(2).unwrap();
+		assert_eq!(view.status, TaskStatus::Doing);
+		assert_eq!(view.reward, 2_000);
+		assert_eq!(view.bids.len(), 1);
+		assert_eq!((view.bids[0].0, view.bids[0].1), (BOB, 50));
+		assert_eq!(view.receiver, Some(BOB));
+		assert!(GrandaoModule::task_view(3).is_none());
+
+		let mut owned = GrandaoModule::tasks_by_owner(ALICE, 0, 10);
+		owned.sort();
+		assert_eq!(owned, vec![1, 2]);
+		assert_eq!(GrandaoModule::tasks_by_owner(ALICE, 1, 1).len(), 1);
+		assert!(GrandaoModule::tasks_by_owner(ALICE, 1, 2).is_empty());
+		assert_eq!(GrandaoModule::tasks_by_receiver(BOB, 0, 10), vec![2]);
+		assert_eq!(GrandaoModule::tasks_by_status(TaskStatus::Bidding, 0, 10), vec![1]);
+	});
+}
+
+#[test]
+fn reputation_is_recorded_and_gates_bidding() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted));
+
+		assert_noop!(GrandaoModule::rate_task(Origin::signed(ALICE), 1, 6), Error::<Test>::InvalidRating);
+		assert_ok!(GrandaoModule::rate_task(Origin::signed(ALICE), 1, 4));
+		assert_noop!(GrandaoModule::rate_task(Origin::signed(ALICE), 1, 5), Error::<Test>::AlreadyRated);
+
+		let reputation = GrandaoModule::reputation(BOB);
+		assert_eq!(reputation.completed, 1);
+		assert_eq!(reputation.average_rating(), 400);
+
+		// 清除任务时同时清除评分 已记录的信誉不受影响
+		assert_ok!(GrandaoModule::remove_closed_task(Origin::signed(ALICE), 1));
+		assert_eq!(GrandaoModule::task_rating(1), None);
+		assert_eq!(GrandaoModule::reputation(BOB).average_rating(), 400);
+
+		assert_ok!(create_task(ALICE, 1_000));
+		let requirement = crate::ReputationRequirement { min_completed: 1, max_abandoned: 0, min_average_rating: 300 };
+		assert_ok!(GrandaoModule::set_bid_policy(Origin::signed(ALICE), 2, crate::BidPolicy::MinReputation(requirement)));
+		assert_noop!(bid(CHARLIE, 2, 50), Error::<Test>::InsufficientReputation);
+		assert_ok!(bid(BOB, 2, 50));
+	});
+}
+
+#[test]
+fn task_reward_can_be_paid_in_project_token() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), PROJECT_TOKEN, 1_000, TITLE.to_vec(), Default::default(),
+			CID.to_vec(), Default::default(), Vec::new(), None, None, Vec::new()));
+		assert_eq!(Currencies::reserved_balance(PROJECT_TOKEN, &ALICE), 1_000);
+		// 存储押金使用原生货币
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), deposit());
+
+		assert_ok!(bid(BOB, 1, 50));
+		assert_eq!(Currencies::reserved_balance(PROJECT_TOKEN, &BOB), 50);
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted));
+
+		assert_eq!(Currencies::free_balance(PROJECT_TOKEN, &ALICE), 9_000);
+		assert_eq!(Currencies::free_balance(PROJECT_TOKEN, &BOB), 2_000);
+		assert_eq!(Currencies::reserved_balance(PROJECT_TOKEN, &BOB), 0);
+		assert_eq!(NativeCurrency::free_balance(&BOB), 100_000);
+	});
+}
+
+#[test]
+fn winning_proposal_becomes_binding_terms() {
+	new_test_ext().execute_with(|| {
+		let deadline = TaskDeadline { bidding_close: None, delivery: Some(100), review_window: None };
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(),
+			CID.to_vec(), deadline, vec![Permill::from_percent(50), Permill::from_percent(50)], None, None, Vec::new()));
+
+		assert_noop!(GrandaoModule::bid_task(Origin::signed(BOB), 1, 50, 1_001, 50, Default::default()),
+			Error::<Test>::InvalidBidPrice);
+		assert_noop!(GrandaoModule::bid_task(Origin::signed(BOB), 1, 50, 600, 101, Default::default()),
+			Error::<Test>::InvalidBidDelivery);
+		assert_ok!(GrandaoModule::bid_task(Origin::signed(BOB), 1, 50, 600, 50, Default::default()));
+		assert_ok!(bid(CHARLIE, 1, 60));
+
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		// 差额退还给任务创建者，里程碑按中标报价重新拆分
+		assert_eq!(GrandaoModule::rel_create_task_stake(1), 600);
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 600 + deposit());
+		let amounts: Vec<u128> = GrandaoModule::task_milestones(1).iter().map(|m| m.amount).collect();
+		assert_eq!(amounts, vec![300, 300]);
+		assert!(GrandaoModule::bid_proposal(1, CHARLIE).is_none());
+
+		// 交付截止区块调整为中标方案的预计交付区块
+		assert_eq!(GrandaoModule::task_deadlines(1).delivery, Some(50));
+		assert!(!crate::DeadlineQueue::<Test>::contains_key(100, 1));
+		assert!(crate::DeadlineQueue::<Test>::contains_key(50, 1));
+	});
+}
+
+#[test]
+fn withdraw_and_amend_bid_while_bidding() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(bid(CHARLIE, 1, 60));
+
+		// 修改投标：追加质押并调整报价
+		assert_ok!(GrandaoModule::amend_bid(Origin::signed(BOB), 1, 80, 700, 500, Default::default()));
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 80);
+		assert_eq!(GrandaoModule::bid_proposal(1, BOB).unwrap().price, 700);
+		assert_noop!(GrandaoModule::amend_bid(Origin::signed(DAVE), 1, 50, 700, 500, Default::default()),
+			Error::<Test>::NoSuchBidder);
+
+		// 撤回投标：扣除10%手续费转入国库
+		assert_ok!(GrandaoModule::withdraw_bid(Origin::signed(CHARLIE), 1));
+		assert_eq!(NativeCurrency::reserved_balance(&CHARLIE), 0);
+		assert_eq!(NativeCurrency::free_balance(&TreasuryAccount::get()), 6);
+		assert!(!GrandaoModule::rel_task_bidder(1).contains(&CHARLIE));
+		assert!(!crate::BidTasks::<Test>::contains_key(CHARLIE, 1));
+		assert_eq!(GrandaoModule::active_bid_count(CHARLIE), 0);
+
+		// 选标后不能再撤回或修改
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_noop!(GrandaoModule::withdraw_bid(Origin::signed(BOB), 1), Error::<Test>::BidClosed);
+		assert_noop!(GrandaoModule::amend_bid(Origin::signed(BOB), 1, 50, 700, 500, Default::default()),
+			Error::<Test>::BidClosed);
+	});
+}
+
+#[test]
+fn withdraw_bid_respects_bidding_close() {
+	new_test_ext().execute_with(|| {
+		let deadline = TaskDeadline { bidding_close: Some(5), delivery: None, review_window: None };
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, Vec::new(), None, None, Vec::new()));
+		assert_noop!(bid(BOB, 1, 100), Error::<Test>::InvalidStakeToken);
+		assert_ok!(bid(BOB, 1, 99));
+
+		// 投标截止后、截止事件处理前也不能撤回
+		System::set_block_number(5);
+		assert_noop!(GrandaoModule::withdraw_bid(Origin::signed(BOB), 1), Error::<Test>::BidClosed);
+	});
+}
+
+#[test]
+fn tasks_are_indexed_by_category_and_tag() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(GrandaoModule::add_category(Origin::signed(ALICE), b"design".to_vec()), sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(GrandaoModule::add_category(Origin::root(), b"design".to_vec()));
+		assert_eq!(GrandaoModule::categories(), vec![(0, b"design".to_vec())]);
+
+		let create = |category, tags: &[&[u8]]| GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(),
+			Default::default(), CID.to_vec(), Default::default(), Vec::new(), None, category, tags.iter().map(|t| t.to_vec()).collect());
+		assert_noop!(create(Some(1), &[]), Error::<Test>::NoSuchCategory);
+		assert_noop!(create(None, &[b"logo", b"logo"]), Error::<Test>::InvalidTag);
+		assert_noop!(create(None, &[b"a", b"b", b"c", b"d"]), Error::<Test>::TooManyTags);
+		assert_ok!(create(Some(0), &[b"logo", b"ui"]));
+
+		// 标签计入存储押金
+		assert_eq!(GrandaoModule::task_detail(1).deposit, deposit() + 6);
+		assert_eq!(GrandaoModule::tasks_by_category(0, 0, 10), vec![1]);
+		assert_eq!(GrandaoModule::tasks_by_tag(b"ui".to_vec(), 0, 10), vec![1]);
+		assert_eq!(GrandaoModule::task_view(1).unwrap().category, Some(0));
+
+		// 撤销任务后清除索引
+		assert_ok!(GrandaoModule::revoke_task(Origin::signed(ALICE), 1));
+		assert!(GrandaoModule::tasks_by_category(0, 0, 10).is_empty());
+		assert!(GrandaoModule::tasks_by_tag(b"logo".to_vec(), 0, 10).is_empty());
+
+		assert_ok!(GrandaoModule::remove_category(Origin::root(), 0));
+		assert_noop!(create(Some(0), &[]), Error::<Test>::NoSuchCategory);
+	});
+}
+
+#[test]
+fn finishing_task_issues_credential_to_receiver() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		let deliverable = H256::repeat_byte(7);
+		assert_noop!(GrandaoModule::submit_deliverable(Origin::signed(CHARLIE), 1, deliverable), Error::<Test>::NotTaskReceiver);
+		assert_ok!(GrandaoModule::submit_deliverable(Origin::signed(BOB), 1, deliverable));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+		assert!(CREDENTIALS.with(|c| c.borrow().is_empty()));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted));
+
+		let credential = crate::TaskCredential { task_id: 1, owner: ALICE, deliverable_hash: deliverable };
+		assert_eq!(CREDENTIALS.with(|c| c.borrow().clone()), vec![(BOB, None, credential)]);
+	});
+}
+
+#[test]
+fn team_task_splits_reward_by_shares() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(bid(CHARLIE, 1, 60));
+
+		assert_noop!(GrandaoModule::delegate_task(Origin::signed(ALICE),
+			vec![(BOB, Permill::from_percent(60)), (CHARLIE, Permill::from_percent(30))], 1), Error::<Test>::InvalidTeam);
+		assert_noop!(GrandaoModule::delegate_task(Origin::signed(ALICE),
+			vec![(BOB, Permill::from_percent(60)), (DAVE, Permill::from_percent(40))], 1), Error::<Test>::NoSuchBidder);
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE),
+			vec![(BOB, Permill::from_percent(60)), (CHARLIE, Permill::from_percent(40))], 1));
+		assert!(crate::ReceivedTasks::<Test>::contains_key(CHARLIE, 1));
+
+		// 团队任何成员都可以交付
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(CHARLIE), 1, TaskStatus::Delivered));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted));
+
+		assert_eq!(NativeCurrency::free_balance(&BOB), 100_600);
+		assert_eq!(NativeCurrency::free_balance(&CHARLIE), 100_400);
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+		assert_eq!(NativeCurrency::reserved_balance(&CHARLIE), 0);
+		assert_eq!(GrandaoModule::reputation(CHARLIE).completed, 1);
+	});
+}
+
+#[test]
+fn subtasks_are_funded_from_parent_escrow() {
+	new_test_ext().execute_with(|| {
+		// 父任务的交付截止区块为中标方案的预计交付区块，子任务不能晚于父任务交付
+		let deadline = TaskDeadline { bidding_close: None, delivery: Some(1_000), review_window: None };
+		let subtask = |owner: AccountId, stake_token: u128| GrandaoModule::create_subtask(Origin::signed(owner), 1, stake_token,
+			TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, Vec::new(), None, None, Vec::new());
+
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_noop!(subtask(BOB, 400), Error::<Test>::InvalidTaskStatus);
+		assert_ok!(bid(BOB, 1, 50));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+
+		// 只有中标者才能创建子任务 子任务的奖励不能超过父任务的锁定奖励
+		assert_noop!(subtask(CHARLIE, 400), Error::<Test>::NotTaskReceiver);
+		assert_noop!(subtask(BOB, 1_001), Error::<Test>::InsufficientEscrow);
+		assert_ok!(subtask(BOB, 400));
+		assert_eq!(GrandaoModule::rel_create_task_stake(1), 600);
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 600 + deposit());
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 50 + 400 + deposit());
+		assert_eq!(GrandaoModule::subtasks(1, 0, 10), vec![2]);
+		assert_eq!(GrandaoModule::task_view(2).unwrap().parent, Some(1));
+		assert_eq!(GrandaoModule::open_subtask_count(1), 1);
+
+		// 子任务结束前父任务不能交付
+		assert_ok!(bid(CHARLIE, 2, 30));
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(BOB), vec![(CHARLIE, Permill::one())], 2));
+		assert_noop!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered), Error::<Test>::OpenSubTasks);
+		assert_noop!(GrandaoModule::open_dispute(Origin::signed(ALICE), 1, Default::default()), Error::<Test>::OpenSubTasks);
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(CHARLIE), 2, TaskStatus::Delivered));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 2, TaskStatus::Accepted));
+		assert_eq!(NativeCurrency::free_balance(&CHARLIE), 100_400);
+
+		// 撤销的子任务奖励退回父任务
+		assert_ok!(subtask(BOB, 100));
+		assert_eq!(GrandaoModule::rel_create_task_stake(1), 500);
+		assert_ok!(GrandaoModule::revoke_task(Origin::signed(BOB), 3));
+		assert_eq!(GrandaoModule::rel_create_task_stake(1), 600);
+		assert_eq!(GrandaoModule::subtasks(1, 0, 10), vec![2]);
+		assert_eq!(GrandaoModule::open_subtask_count(1), 0);
+
+		// 结算父任务时只支付剩余的任务奖励
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(BOB), 1, TaskStatus::Delivered));
+		assert_ok!(GrandaoModule::update_task_status(Origin::signed(ALICE), 1, TaskStatus::Accepted));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), deposit());
+		assert_eq!(NativeCurrency::free_balance(&ALICE), 99_000 - deposit());
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), deposit());
+		assert_eq!(NativeCurrency::free_balance(&BOB), 100_600 - deposit());
+	});
+}
+
+#[test]
+fn bid_policy_restricts_bidders() {
+	new_test_ext().execute_with(|| {
+		use crate::BidPolicy;
+
+		assert_ok!(create_task(ALICE, 1_000));
+		assert_noop!(GrandaoModule::set_bid_policy(Origin::signed(BOB), 1, BidPolicy::Open), Error::<Test>::NotTaskOwner);
+		assert_noop!(GrandaoModule::set_bid_policy(Origin::signed(ALICE), 1, BidPolicy::Allowlist(vec![BOB, CHARLIE, DAVE, 5])),
+			Error::<Test>::TooManyAllowlisted);
+
+		assert_ok!(GrandaoModule::set_bid_policy(Origin::signed(ALICE), 1, BidPolicy::Allowlist(vec![BOB])));
+		assert_noop!(bid(CHARLIE, 1, 50), Error::<Test>::NotAllowlisted);
+		assert_ok!(bid(BOB, 1, 50));
+
+		assert_ok!(GrandaoModule::set_bid_policy(Origin::signed(ALICE), 1, BidPolicy::ClassHolders(7)));
+		assert_eq!(GrandaoModule::task_view(1).unwrap().bid_policy, BidPolicy::ClassHolders(7));
+		assert_noop!(bid(CHARLIE, 1, 50), Error::<Test>::NotClassHolder);
+		NFT_HOLDERS.with(|h| h.borrow_mut().push((7, CHARLIE)));
+		assert_ok!(bid(CHARLIE, 1, 50));
+
+		// 取消限制后为开放投标
+		assert_ok!(GrandaoModule::set_bid_policy(Origin::signed(ALICE), 1, BidPolicy::Open));
+		assert!(!crate::TaskBidPolicy::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn sealed_bids_are_committed_then_revealed() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		let deadline = TaskDeadline { bidding_close: Some(5), delivery: Some(100), review_window: None };
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, Vec::new(), None, None, Vec::new()));
+		assert_noop!(GrandaoModule::set_sealed_bidding(Origin::signed(ALICE), 1, Some(5)), Error::<Test>::InvalidDeadline);
+		assert_ok!(GrandaoModule::set_sealed_bidding(Origin::signed(ALICE), 1, Some(10)));
+
+		// 提交阶段只提交报价哈希
+		let (bob_salt, charlie_salt) = (H256::repeat_byte(1), H256::repeat_byte(2));
+		assert_noop!(bid(BOB, 1, 50), Error::<Test>::SealedTask);
+		assert_ok!(GrandaoModule::commit_bid(Origin::signed(BOB), 1, 50, BlakeTwo256::hash_of(&(1u128, BOB, 600u128, bob_salt))));
+		assert_ok!(GrandaoModule::commit_bid(Origin::signed(CHARLIE), 1, 60, BlakeTwo256::hash_of(&(1u128, CHARLIE, 700u128, charlie_salt))));
+		assert_noop!(GrandaoModule::set_sealed_bidding(Origin::signed(ALICE), 1, None), Error::<Test>::BiddingModeLocked);
+		assert_noop!(GrandaoModule::reveal_bid(Origin::signed(BOB), 1, 600, bob_salt, 50, Default::default()), Error::<Test>::NotRevealPhase);
+
+		// 揭示阶段
+		run_to_block(5);
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Bidding));
+		assert_noop!(GrandaoModule::reveal_bid(Origin::signed(BOB), 1, 500, bob_salt, 50, Default::default()), Error::<Test>::InvalidReveal);
+		assert_ok!(GrandaoModule::reveal_bid(Origin::signed(BOB), 1, 600, bob_salt, 50, Default::default()));
+		assert_eq!(GrandaoModule::bid_proposal(1, BOB).map(|p| p.price), Some(600));
+		assert_noop!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1), Error::<Test>::RevealNotClosed);
+
+		// 揭示截止后罚没未揭示的投标，创建者才能选标
+		run_to_block(10);
+		assert!(!GrandaoModule::rel_task_bidder(1).contains(&CHARLIE));
+		assert_eq!(NativeCurrency::free_balance(&CHARLIE), 99_970);
+		assert_eq!(NativeCurrency::free_balance(&TreasuryAccount::get()), 30);
+		assert_ok!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(BOB, Permill::one())], 1));
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 600 + deposit());
+	});
+}
+
+#[test]
+fn auto_award_picks_lowest_qualified_bid() {
+	new_test_ext().execute_with(|| {
+		let deadline = TaskDeadline { bidding_close: Some(5), delivery: None, review_window: None };
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, Vec::new(), None, None, Vec::new()));
+		assert_ok!(GrandaoModule::set_auto_award(Origin::signed(ALICE), 1, Some(Default::default())));
+
+		// BOB报价最低但有违约记录；CHARLIE与DAVE报价相同，CHARLIE先投标
+		crate::Reputations::<Test>::mutate(BOB, |r| r.abandoned = 1);
+		assert_ok!(GrandaoModule::bid_task(Origin::signed(BOB), 1, 50, 500, 50, Default::default()));
+		assert_ok!(GrandaoModule::bid_task(Origin::signed(CHARLIE), 1, 50, 600, 50, Default::default()));
+		assert_ok!(GrandaoModule::bid_task(Origin::signed(DAVE), 1, 5, 600, 50, Default::default()));
+		assert_noop!(GrandaoModule::delegate_task(Origin::signed(ALICE), vec![(CHARLIE, Permill::one())], 1), Error::<Test>::AutoAwardTask);
+
+		run_to_block(5);
+
+		assert_eq!(GrandaoModule::rel_task_status(1), Some(TaskStatus::Doing));
+		assert_eq!(GrandaoModule::rel_task_receiver(1), CHARLIE);
+		assert_eq!(GrandaoModule::rel_create_task_stake(1), 600);
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 600 + deposit());
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 0);
+		assert_eq!(NativeCurrency::reserved_balance(&DAVE), 0);
+		assert_eq!(NativeCurrency::reserved_balance(&CHARLIE), 50);
+	});
+}
+
+#[test]
+fn amending_bid_resets_auto_award_order() {
+	new_test_ext().execute_with(|| {
+		let deadline = TaskDeadline { bidding_close: Some(5), delivery: None, review_window: None };
+		assert_ok!(GrandaoModule::create_task(Origin::signed(ALICE), NATIVE, 1_000, TITLE.to_vec(), Default::default(), CID.to_vec(), deadline, Vec::new(), None, None, Vec::new()));
+		assert_ok!(GrandaoModule::set_auto_award(Origin::signed(ALICE), 1, Some(Default::default())));
+
+		// CHARLIE先投标，修改投标后排在DAVE之后
+		assert_ok!(GrandaoModule::bid_task(Origin::signed(CHARLIE), 1, 50, 600, 50, Default::default()));
+		assert_ok!(GrandaoModule::bid_task(Origin::signed(DAVE), 1, 5, 600, 50, Default::default()));
+		assert_ok!(GrandaoModule::amend_bid(Origin::signed(CHARLIE), 1, 50, 600, 40, Default::default()));
+		assert!(GrandaoModule::bid_sequence(1, CHARLIE) > GrandaoModule::bid_sequence(1, DAVE));
+
+		run_to_block(5);
+
+		assert_eq!(GrandaoModule::rel_task_receiver(1), DAVE);
+	});
+}
+
+#[test]
+fn migration_reserves_legacy_stakes() {
+	new_test_ext().execute_with(|| {
+		use frame_support::{StorageValue, storage::unhashed};
+
+		// 旧版本的任务：1 投标中、2 创建者余额不足、3 已结束 质押均未实际锁定
+		for (task_id, owner, status) in [(1u128, ALICE, 1u8), (2, DAVE, 1), (3, ALICE, 8)].iter() {
+			unhashed::put(&crate::TaskDetail::<Test>::hashed_key_for(task_id), &(*owner, b"{}".to_vec(), 1u64));
+			unhashed::put(&crate::RelTaskStatus::hashed_key_for(task_id), status);
+			crate::RelCreateTaskStake::insert(task_id, 1_000);
+		}
+		crate::StorageVersion::put(crate::Releases::V1);
+
+		crate::migrations::migrate::<Test>();
+
+		assert_eq!(NativeCurrency::reserved_balance(&ALICE), 1_000);
+		assert_eq!(GrandaoModule::rel_create_task_stake(1), 1_000);
+		assert_eq!(GrandaoModule::rel_create_task_stake(2), 0);
+		assert_eq!(GrandaoModule::unbacked_stake(2, DAVE), 1_000);
+		assert_eq!(GrandaoModule::rel_create_task_stake(3), 0);
+		assert_eq!(GrandaoModule::unbacked_stake(3, ALICE), 0);
+	});
+}
+
+#[test]
+fn migration_reserves_legacy_bid_stakes() {
+	new_test_ext().execute_with(|| {
+		use frame_support::{StorageValue, storage::unhashed};
+
+		// 任务1投标中；任务2进行中，CHARLIE中标、DAVE未中标
+		for (task_id, status) in [(1u128, 1u8), (2, 2)].iter() {
+			unhashed::put(&crate::TaskDetail::<Test>::hashed_key_for(task_id), &(ALICE, b"{}".to_vec(), 1u64));
+			unhashed::put(&crate::RelTaskStatus::hashed_key_for(task_id), status);
+		}
+		crate::RelBidTaskStake::<Test>::insert(1, BOB, 100);
+		crate::RelBidTaskStake::<Test>::insert(1, DAVE, 100);
+		crate::RelBidTaskStake::<Test>::insert(2, CHARLIE, 100);
+		crate::RelBidTaskStake::<Test>::insert(2, DAVE, 100);
+		crate::RelTaskReceiver::<Test>::insert(2, CHARLIE);
+		crate::StorageVersion::put(crate::Releases::V1);
+
+		crate::migrations::migrate::<Test>();
+
+		assert_eq!(NativeCurrency::reserved_balance(&BOB), 100);
+		assert_eq!(NativeCurrency::reserved_balance(&CHARLIE), 100);
+		assert_eq!(GrandaoModule::rel_bid_stake(1, DAVE), 0);
+		assert_eq!(GrandaoModule::unbacked_stake(1, DAVE), 100);
+		assert!(!crate::RelBidTaskStake::<Test>::contains_key(2, DAVE));
+		assert_eq!(GrandaoModule::active_bid_count(BOB), 1);
+		assert_eq!(GrandaoModule::active_bid_count(DAVE), 1);
+	});
+}
+
+#[test]
+fn migration_skips_and_clears_orphaned_tasks() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{StorageValue, storage::migration::put_storage_value};
+
+		// 旧版本撤销任务9时只删除了任务详情
+		crate::RelCreateTaskStake::insert(9, 1_000);
+		crate::RelBidTaskStake::<Test>::insert(9, BOB, 50);
+		crate::RelTaskBidder::<Test>::insert(9, [BOB].iter().cloned().collect::<std::collections::BTreeSet<_>>());
+		let key = [sp_io::hashing::blake2_128(&BOB.encode()).to_vec(), BOB.encode()].concat();
+		put_storage_value(b"GrandaoModule", b"MyBidTasks", &key, [9u128].iter().cloned().collect::<std::collections::BTreeSet<_>>());
+		crate::StorageVersion::put(crate::Releases::V4);
+
+		crate::migrations::migrate::<Test>();
+
+		assert!(!crate::RelCreateTaskStake::contains_key(9));
+		assert!(!crate::RelBidTaskStake::<Test>::contains_key(9, BOB));
+		assert!(!crate::RelTaskBidder::<Test>::contains_key(9));
+		assert!(!crate::BidTasks::<Test>::contains_key(BOB, 9));
+		assert_eq!(GrandaoModule::active_bid_count(BOB), 0);
+	});
+}
@@ -121,10 +121,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("template-node"),
 	impl_name: create_runtime_str!("template-node"),
 	authoring_version: 1,
-	spec_version: 4,
+	spec_version: 5,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 
@@ -338,9 +338,90 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MinArbitratorStake: Balance = 100 * DOLLARS;
+	pub const DisputeBond: Balance = 10 * DOLLARS;
+	pub const ArbitratorPanelSize: u32 = 3;
+	pub const MaxArbitrators: u32 = 100;
+	pub const MaxEvidence: u32 = 20;
+	pub const AppealFee: Balance = 20 * DOLLARS;
+	pub const AppealPeriod: BlockNumber = 3 * DAYS;
+	pub const RulingPeriod: BlockNumber = 7 * DAYS;
+	pub const MaxDeadlinesPerBlock: u32 = 50;
+	pub const MaxDeadlineRetries: u32 = 10;
+	pub const ReviewPeriod: BlockNumber = 7 * DAYS;
+	pub const MaxMilestones: u32 = 16;
+	pub const MaxTitleLength: u32 = 128;
+	pub const MaxUriLength: u32 = 128;
+	pub const TaskDepositPerByte: Balance = 10 * MILLICENTS;
+	pub const MaxOpenTasksPerAccount: u32 = 100;
+	pub const MaxActiveBidsPerAccount: u32 = 100;
+	pub const MaxBiddersPerTask: u32 = 100;
+	pub const BidWithdrawalFee: Permill = Permill::from_percent(5);
+	pub const MaxTagsPerTask: u32 = 8;
+	pub const MaxTagLength: u32 = 32;
+	pub const MaxTeamSize: u32 = 10;
+	pub const MaxBidAllowlist: u32 = 50;
+	pub const UnrevealedBidPenalty: Permill = Permill::from_percent(20);
+}
+
 /// Configure the grandao pallet in pallets/grandao.
 impl pallet_grandao::Config for Runtime {
 	type Event = Event;
+	type Currency = Currency<Runtime, GetNativeCurrencyId>;
+	type MultiCurrency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type Randomness = RandomnessCollectiveFlip;
+	type MinArbitratorStake = MinArbitratorStake;
+	type DisputeBond = DisputeBond;
+	type ArbitratorPanelSize = ArbitratorPanelSize;
+	type MaxArbitrators = MaxArbitrators;
+	type MaxEvidence = MaxEvidence;
+	type JudgeOrigin = pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>;
+	type AppealFee = AppealFee;
+	type AppealPeriod = AppealPeriod;
+	type RulingPeriod = RulingPeriod;
+	type TreasuryAccount = TreasuryModuleAccount;
+	type MaxDeadlinesPerBlock = MaxDeadlinesPerBlock;
+	type MaxDeadlineRetries = MaxDeadlineRetries;
+	type ReviewPeriod = ReviewPeriod;
+	type MaxMilestones = MaxMilestones;
+	type MaxTitleLength = MaxTitleLength;
+	type MaxUriLength = MaxUriLength;
+	type DepositPerByte = TaskDepositPerByte;
+	type MaxOpenTasksPerAccount = MaxOpenTasksPerAccount;
+	type MaxActiveBidsPerAccount = MaxActiveBidsPerAccount;
+	type MaxBiddersPerTask = MaxBiddersPerTask;
+	type BidWithdrawalFee = BidWithdrawalFee;
+	type CategoryOrigin = pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>;
+	type MaxTagsPerTask = MaxTagsPerTask;
+	type MaxTagLength = MaxTagLength;
+	type Credentials = GrandaoCredentials;
+	type MaxTeamSize = MaxTeamSize;
+	type NftHolders = GrandaoNftHolders;
+	type MaxBidAllowlist = MaxBidAllowlist;
+	type UnrevealedBidPenalty = UnrevealedBidPenalty;
+}
+
+/// 通过gdnft为完成任务的中标者铸造不可转让的凭证NFT，每个任务分类对应一个凭证class
+pub struct GrandaoCredentials;
+impl pallet_grandao::CredentialMinter<AccountId, Hash> for GrandaoCredentials {
+	fn mint_credential(
+		receiver: &AccountId,
+		category: Option<u32>,
+		credential: pallet_grandao::TaskCredential<AccountId, Hash>,
+	) -> frame_support::dispatch::DispatchResult {
+		use codec::Encode;
+		NftModule::mint_soulbound(category, receiver, credential.encode()).map(|_| ())
+	}
+}
+
+/// 通过gdnft查询账户是否持有某NFT class的token，用于按NFT限制投标的任务
+pub struct GrandaoNftHolders;
+impl pallet_grandao::ClassHolder<AccountId> for GrandaoNftHolders {
+	fn holds(class_id: u32, who: &AccountId) -> bool {
+		NftModule::holds_class(who, class_id)
+	}
 }
 
 parameter_type_with_key! {
@@ -893,7 +974,7 @@ construct_runtime!(
 
 		// Include the custom logic from the template pallet in the runtime.
 		TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
-		GrandaoModule: pallet_grandao::{Module, Call, Storage, Event<T>},
+		GrandaoModule: pallet_grandao::{Module, Call, Storage, Config, Event<T>},
 		NftModule: pallet_gdnft::{Module, Call ,Storage, Event<T>},
 
 		// ORML related modules
@@ -1113,6 +1194,52 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_grandao_rpc_runtime_api::GrandaoApi<Block, AccountId, Hash, BlockNumber, CurrencyId> for Runtime {
+		fn task(task_id: u128) -> Option<pallet_grandao::TaskView<AccountId, Hash, BlockNumber, CurrencyId>> {
+			GrandaoModule::task_view(task_id)
+		}
+
+		fn milestones(task_id: u128) -> Vec<pallet_grandao::Milestone> {
+			GrandaoModule::task_milestones(task_id)
+		}
+
+		fn tasks_by_owner(owner: AccountId, page: u32, page_size: u32) -> Vec<u128> {
+			GrandaoModule::tasks_by_owner(owner, page, page_size)
+		}
+
+		fn tasks_by_bidder(bidder: AccountId, page: u32, page_size: u32) -> Vec<u128> {
+			GrandaoModule::tasks_by_bidder(bidder, page, page_size)
+		}
+
+		fn tasks_by_receiver(receiver: AccountId, page: u32, page_size: u32) -> Vec<u128> {
+			GrandaoModule::tasks_by_receiver(receiver, page, page_size)
+		}
+
+		fn tasks_by_status(status: pallet_grandao::TaskStatus, page: u32, page_size: u32) -> Vec<u128> {
+			GrandaoModule::tasks_by_status(status, page, page_size)
+		}
+
+		fn tasks_by_category(category: u32, page: u32, page_size: u32) -> Vec<u128> {
+			GrandaoModule::tasks_by_category(category, page, page_size)
+		}
+
+		fn tasks_by_tag(tag: Vec<u8>, page: u32, page_size: u32) -> Vec<u128> {
+			GrandaoModule::tasks_by_tag(tag, page, page_size)
+		}
+
+		fn subtasks(parent_id: u128, page: u32, page_size: u32) -> Vec<u128> {
+			GrandaoModule::subtasks(parent_id, page, page_size)
+		}
+
+		fn categories() -> Vec<(u32, Vec<u8>)> {
+			GrandaoModule::categories()
+		}
+
+		fn reputation(who: AccountId) -> pallet_grandao::Reputation {
+			GrandaoModule::reputation(who)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(